@@ -1,8 +1,13 @@
 use crate::{
     ntt::{intt, ntt},
-    Field,
+    poly, Field,
 };
 
+/// The BabyBear prime `15 · 2^27 + 1`, used next to Goldilocks for the CRT.
+const Q: u64 = 2_013_265_921;
+const Q_GENERATOR: u64 = 31;
+const Q_TWO_ADICITY: u32 = 27;
+
 /// In-place circular convolution.
 ///
 /// Expects `b` to be in NTT form.
@@ -37,9 +42,198 @@ pub fn circular_naive(a: &[Field], b: &[Field]) -> Vec<Field> {
     result
 }
 
+/// Multiply two unsigned integers given as little-endian `u64` limbs.
+///
+/// The limbs are split in 32-bit digits and convolved with NTTs modulo both
+/// Goldilocks and BabyBear. Each coefficient is less than `2^94`, which the
+/// CRT recovers exactly from the two residues as their product exceeds `2^95`.
+/// The carries are then propagated.
+///
+/// Returns the product as little-endian `u128` limbs, `(a.len() + b.len()) / 2`
+/// rounded up of them.
+///
+/// # Panics
+///
+/// Panics if the product has more than `2^26` limbs of `u64`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)] // Taking the low 32 bits.
+pub fn big_integer_mul(a: &[u64], b: &[u64]) -> Vec<u128> {
+    let mut result = vec![0; (a.len() + b.len()).div_ceil(2)];
+    if a.is_empty() || b.is_empty() {
+        return result;
+    }
+    let a = digits(a);
+    let b = digits(b);
+    assert!(
+        a.len() + b.len() - 1 <= 1 << Q_TWO_ADICITY,
+        "Product too large for a BabyBear NTT."
+    );
+
+    let field = |digits: &[u64]| digits.iter().map(|&d| Field::from(d)).collect::<Vec<_>>();
+    let residues_p = poly::mul(&field(&a), &field(&b));
+    let residues_q = mul_q(&a, &b);
+
+    let p_inv = pow_q(Field::MODULUS % Q, Q - 2);
+    let mut carry = 0_u128;
+    let mut digits = Vec::with_capacity(4 * result.len());
+    for (&r_p, &r_q) in residues_p.iter().zip(&residues_q) {
+        let r_p = u64::from(r_p);
+        let t = (r_q + Q - r_p % Q) % Q * p_inv % Q;
+        carry += u128::from(r_p) + u128::from(Field::MODULUS) * u128::from(t);
+        digits.push(carry as u32);
+        carry >>= 32;
+    }
+    while digits.len() < 4 * result.len() {
+        digits.push(carry as u32);
+        carry >>= 32;
+    }
+    debug_assert_eq!(carry, 0);
+    for (limb, digits) in result.iter_mut().zip(digits.chunks_exact(4)) {
+        *limb = digits
+            .iter()
+            .rev()
+            .fold(0, |limb, &digit| limb << 32 | u128::from(digit));
+    }
+    result
+}
+
+fn digits(limbs: &[u64]) -> Vec<u64> {
+    limbs
+        .iter()
+        .flat_map(|&limb| [limb & 0xffff_ffff, limb >> 32])
+        .collect()
+}
+
+const fn pow_q(mut base: u64, mut exponent: u64) -> u64 {
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % Q;
+        }
+        base = base * base % Q;
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Radix-2 NTT modulo [`Q`] on canonical values, in natural order.
+fn ntt_q(values: &mut [u64], root: u64) {
+    let n = values.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i
+            .reverse_bits()
+            .checked_shr(usize::BITS - bits)
+            .unwrap_or(0);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    let mut half = 1;
+    while half < n {
+        let step = pow_q(root, (n / (2 * half)) as u64);
+        for chunk in values.chunks_exact_mut(2 * half) {
+            let (low, high) = chunk.split_at_mut(half);
+            let mut twiddle = 1;
+            for (x, y) in low.iter_mut().zip(high) {
+                let t = *y * twiddle % Q;
+                *y = (*x + Q - t) % Q;
+                *x = (*x + t) % Q;
+                twiddle = twiddle * step % Q;
+            }
+        }
+        half *= 2;
+    }
+}
+
+/// Linear convolution modulo [`Q`].
+fn mul_q(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+    let root = pow_q(Q_GENERATOR, (Q - 1) / size as u64);
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.resize(size, 0);
+    b.resize(size, 0);
+    ntt_q(&mut a, root);
+    ntt_q(&mut b, root);
+    for (a, b) in a.iter_mut().zip(&b) {
+        *a = *a * b % Q;
+    }
+    ntt_q(&mut a, pow_q(root, Q - 2));
+    let scale = pow_q(size as u64, Q - 2);
+    a.truncate(len);
+    for a in &mut a {
+        *a = *a * scale % Q;
+    }
+    a
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num_bigint::BigUint;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn to_big(limbs: &[u64]) -> BigUint {
+        BigUint::from_bytes_le(
+            &limbs
+                .iter()
+                .flat_map(|l| l.to_le_bytes())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn to_big_u128(limbs: &[u128]) -> BigUint {
+        BigUint::from_bytes_le(
+            &limbs
+                .iter()
+                .flat_map(|l| l.to_le_bytes())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_big_integer_mul() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (n, m) in [
+            (0_usize, 3_usize),
+            (1, 1),
+            (1, 2),
+            (3, 5),
+            (100, 77),
+            (1000, 1000),
+        ] {
+            let a = (0..n).map(|_| rng.gen()).collect::<Vec<u64>>();
+            let b = (0..m).map(|_| rng.gen()).collect::<Vec<u64>>();
+            let result = big_integer_mul(&a, &b);
+            assert_eq!(result.len(), (n + m).div_ceil(2));
+            assert_eq!(to_big_u128(&result), to_big(&a) * to_big(&b));
+        }
+    }
+
+    #[test]
+    fn test_big_integer_mul_max() {
+        // Largest digits maximize the coefficients and carries.
+        let a = vec![u64::MAX; 1 << 12];
+        let result = big_integer_mul(&a, &a);
+        assert_eq!(to_big_u128(&result), to_big(&a) * to_big(&a));
+    }
+
+    #[test]
+    fn test_ntt_q() {
+        let mut rng = StdRng::seed_from_u64(Q);
+        let a = (0..20).map(|_| rng.gen_range(0..Q)).collect::<Vec<_>>();
+        let b = (0..13).map(|_| rng.gen_range(0..Q)).collect::<Vec<_>>();
+        let mut expected = vec![0_u64; 32];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                expected[i + j] = (expected[i + j] + x * y) % Q;
+            }
+        }
+        assert_eq!(mul_q(&a, &b), expected);
+        assert_eq!(pow_q(Q_GENERATOR, (Q - 1) / 2), Q - 1);
+    }
 
     #[test]
     fn test_circular_naive() {
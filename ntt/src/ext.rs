@@ -0,0 +1,416 @@
+//! Extension fields `GF(p²) = GF(p)[u] / (u² - 7)` and `GF(p³) = GF(p)[u] /
+//! (u³ - 2)`.
+//!
+//! Seven is a quadratic non-residue and two a cubic non-residue modulo `p`, so
+//! both polynomials are irreducible.
+
+use crate::Field;
+use core::{fmt, ops};
+
+/// An element `a + b·u` of the quadratic extension, with `u² = 7`.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Field2 {
+    pub a: Field,
+    pub b: Field,
+}
+
+impl Field2 {
+    /// The non-residue `u² = 7`.
+    pub const NON_RESIDUE: u64 = 7;
+
+    #[must_use]
+    pub const fn new(a: Field, b: Field) -> Self {
+        Self { a, b }
+    }
+
+    /// The conjugate `a - b·u`.
+    #[must_use]
+    pub fn conjugate(self) -> Self {
+        Self::new(self.a, -self.b)
+    }
+
+    /// The Frobenius map `x ↦ x^p`, which for this extension is conjugation
+    /// since `u^p = 7^((p - 1) / 2) · u = -u`.
+    #[must_use]
+    pub fn frobenius(self) -> Self {
+        self.conjugate()
+    }
+
+    /// The norm `x · x^p = a² - 7 b²` in the base field.
+    #[must_use]
+    pub fn norm(self) -> Field {
+        self.a * self.a - Field::from(Self::NON_RESIDUE) * self.b * self.b
+    }
+
+    /// Inverse of the element, or zero.
+    #[must_use]
+    pub fn inv(self) -> Self {
+        let norm_inv = self.norm().inv();
+        Self::new(self.a * norm_inv, -self.b * norm_inv)
+    }
+}
+
+impl fmt::Debug for Field2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} + {:?}·u", self.a, self.b)
+    }
+}
+
+impl From<Field> for Field2 {
+    fn from(value: Field) -> Self {
+        Self::new(value, Field::from(0))
+    }
+}
+
+impl ops::Add for Field2 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.a + rhs.a, self.b + rhs.b)
+    }
+}
+
+impl ops::Neg for Field2 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self::new(-self.a, -self.b)
+    }
+}
+
+impl ops::Sub for Field2 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.a - rhs.a, self.b - rhs.b)
+    }
+}
+
+impl ops::Mul for Field2 {
+    type Output = Self;
+
+    /// Karatsuba: three base field multiplications and one by the constant.
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        let aa = self.a * rhs.a;
+        let bb = self.b * rhs.b;
+        let cross = (self.a + self.b) * (rhs.a + rhs.b) - aa - bb;
+        Self::new(aa + Field::from(Self::NON_RESIDUE) * bb, cross)
+    }
+}
+
+/// Derive the assigning and division operators from `+`, `-`, `*` and
+/// `inv`.
+macro_rules! impl_derived_ops {
+    ($type:ty) => {
+        impl ops::AddAssign for $type {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl ops::SubAssign for $type {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl ops::MulAssign for $type {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl ops::Div for $type {
+            type Output = Self;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            #[inline(always)]
+            fn div(self, rhs: Self) -> Self {
+                self * rhs.inv()
+            }
+        }
+
+        impl ops::DivAssign for $type {
+            #[inline(always)]
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+    };
+}
+
+impl_derived_ops!(Field2);
+impl_derived_ops!(Field3);
+
+/// An element `a + b·u + c·u²` of the cubic extension, with `u³ = 2`.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Field3 {
+    pub a: Field,
+    pub b: Field,
+    pub c: Field,
+}
+
+impl Field3 {
+    /// The non-residue `u³ = 2`.
+    pub const NON_RESIDUE: u64 = 2;
+
+    #[must_use]
+    pub const fn new(a: Field, b: Field, c: Field) -> Self {
+        Self { a, b, c }
+    }
+
+    /// The Frobenius map `x ↦ x^p`. Since `u^p = 2^((p - 1) / 3) · u = ω u`
+    /// with `ω` a primitive cube root of unity, this scales `b` by `ω` and `c`
+    /// by `ω²`.
+    #[must_use]
+    pub fn frobenius(self) -> Self {
+        let omega = Field::from(Self::NON_RESIDUE).pow((Field::MODULUS - 1) / 3);
+        Self::new(self.a, self.b * omega, self.c * omega * omega)
+    }
+
+    /// Inverse of the element, or zero.
+    ///
+    /// Multiplies by the adjugate `(a² - 2bc) + (2c² - ab)·u + (b² - ac)·u²`,
+    /// which turns the element into its norm in the base field.
+    #[allow(clippy::suspicious_operation_groupings)]
+    #[must_use]
+    pub fn inv(self) -> Self {
+        let k = Field::from(Self::NON_RESIDUE);
+        let Self { a, b, c } = self;
+        let adjugate = Self::new(a * a - k * b * c, k * c * c - a * b, b * b - a * c);
+        let norm = a * adjugate.a + k * (c * adjugate.b + b * adjugate.c);
+        let norm_inv = norm.inv();
+        Self::new(
+            adjugate.a * norm_inv,
+            adjugate.b * norm_inv,
+            adjugate.c * norm_inv,
+        )
+    }
+}
+
+impl fmt::Debug for Field3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} + {:?}·u + {:?}·u²", self.a, self.b, self.c)
+    }
+}
+
+impl From<Field> for Field3 {
+    fn from(value: Field) -> Self {
+        Self::new(value, Field::from(0), Field::from(0))
+    }
+}
+
+impl ops::Add for Field3 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.a + rhs.a, self.b + rhs.b, self.c + rhs.c)
+    }
+}
+
+impl ops::Neg for Field3 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self::new(-self.a, -self.b, -self.c)
+    }
+}
+
+impl ops::Sub for Field3 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.a - rhs.a, self.b - rhs.b, self.c - rhs.c)
+    }
+}
+
+impl ops::Mul for Field3 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        let k = Field::from(Self::NON_RESIDUE);
+        let x = [self.a, self.b, self.c];
+        let y = [rhs.a, rhs.b, rhs.c];
+        Self::new(
+            x[0] * y[0] + k * (x[1] * y[2] + x[2] * y[1]),
+            x[0] * y[1] + x[1] * y[0] + k * x[2] * y[2],
+            x[0] * y[2] + x[1] * y[1] + x[2] * y[0],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly;
+    use proptest::proptest;
+
+    /// The modulus `u² - 7` as a polynomial.
+    fn modulus() -> Vec<Field> {
+        vec![
+            -Field::from(Field2::NON_RESIDUE),
+            Field::from(0),
+            Field::from(1),
+        ]
+    }
+
+    fn from_poly(mut coefficients: Vec<Field>) -> Field2 {
+        coefficients.resize(2, Field::from(0));
+        Field2::new(coefficients[0], coefficients[1])
+    }
+
+    #[test]
+    fn test_non_residue() {
+        assert_eq!(Field::from(Field2::NON_RESIDUE).legendre(), -1);
+    }
+
+    #[test]
+    fn test_mul() {
+        proptest!(|(a: Field, b: Field, c: Field, d: Field)| {
+            let product = poly::mul_naive(&[a, b], &[c, d]);
+            let (_, rem) = poly::div_rem(&product, &modulus());
+            assert_eq!(Field2::new(a, b) * Field2::new(c, d), from_poly(rem));
+        });
+    }
+
+    #[test]
+    fn test_inv() {
+        let zero = Field2::default();
+        let one = Field2::from(Field::from(1));
+        assert_eq!(zero.inv(), zero);
+        proptest!(|(a: Field, b: Field)| {
+            let x = Field2::new(a, b);
+            if x != zero {
+                let (_, s, _) = poly::xgcd(&[a, b], &modulus());
+                assert_eq!(x.inv(), from_poly(s));
+                assert_eq!(x * x.inv(), one);
+            }
+        });
+    }
+
+    #[test]
+    fn test_frobenius() {
+        proptest!(|(a: Field, b: Field)| {
+            let x = Field2::new(a, b);
+            let mut power = Field2::from(Field::from(1));
+            let mut base = x;
+            let mut exp = Field::MODULUS;
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    power *= base;
+                }
+                base *= base;
+                exp >>= 1;
+            }
+            assert_eq!(x.frobenius(), power);
+            assert_eq!(Field2::from(x.norm()), x * x.frobenius());
+        });
+    }
+
+    #[test]
+    fn test_embedding() {
+        proptest!(|(a: Field, b: Field)| {
+            let (x, y) = (Field2::from(a), Field2::from(b));
+            assert_eq!(x + y, Field2::from(a + b));
+            assert_eq!(x - y, Field2::from(a - b));
+            assert_eq!(x * y, Field2::from(a * b));
+            assert_eq!(-x, Field2::from(-a));
+        });
+    }
+
+    /// The modulus `u³ - 2` as a polynomial.
+    fn modulus3() -> Vec<Field> {
+        let zero = Field::from(0);
+        vec![
+            -Field::from(Field3::NON_RESIDUE),
+            zero,
+            zero,
+            Field::from(1),
+        ]
+    }
+
+    fn from_poly3(mut coefficients: Vec<Field>) -> Field3 {
+        coefficients.resize(3, Field::from(0));
+        Field3::new(coefficients[0], coefficients[1], coefficients[2])
+    }
+
+    #[test]
+    fn test_cubic_non_residue() {
+        let k = Field::from(Field3::NON_RESIDUE);
+        assert_ne!(k.pow((Field::MODULUS - 1) / 3), Field::from(1));
+    }
+
+    #[test]
+    fn test_mul3() {
+        proptest!(|(x: [Field; 3], y: [Field; 3])| {
+            let product = poly::mul_naive(&x, &y);
+            let (_, rem) = poly::div_rem(&product, &modulus3());
+            let x = Field3::new(x[0], x[1], x[2]);
+            let y = Field3::new(y[0], y[1], y[2]);
+            assert_eq!(x * y, from_poly3(rem));
+        });
+    }
+
+    #[test]
+    fn test_inv3() {
+        let zero = Field3::default();
+        let one = Field3::from(Field::from(1));
+        assert_eq!(zero.inv(), zero);
+        proptest!(|(x: [Field; 3], base: Field)| {
+            let [a, b, c] = x;
+            let element = Field3::new(a, b, c);
+            if element != zero {
+                let (_, s, _) = poly::xgcd(&x, &modulus3());
+                assert_eq!(element.inv(), from_poly3(s));
+                assert_eq!(element * element.inv(), one);
+                assert_eq!(element / element, one);
+            }
+            if base != Field::from(0) {
+                let element = Field3::from(base);
+                assert_eq!(element.inv(), Field3::from(base.inv()));
+                assert_eq!(element * element.inv(), one);
+            }
+        });
+    }
+
+    #[test]
+    fn test_frobenius3() {
+        proptest!(|(x: [Field; 3])| {
+            let [a, b, c] = x;
+            let element = Field3::new(a, b, c);
+            let mut power = Field3::from(Field::from(1));
+            let mut base = element;
+            let mut exp = Field::MODULUS;
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    power *= base;
+                }
+                base *= base;
+                exp >>= 1;
+            }
+            assert_eq!(element.frobenius(), power);
+        });
+    }
+
+    #[test]
+    fn test_embedding3() {
+        proptest!(|(a: Field, b: Field)| {
+            let (x, y) = (Field3::from(a), Field3::from(b));
+            assert_eq!(x + y, Field3::from(a + b));
+            assert_eq!(x - y, Field3::from(a - b));
+            assert_eq!(x * y, Field3::from(a * b));
+            assert_eq!(-x, Field3::from(-a));
+        });
+    }
+}
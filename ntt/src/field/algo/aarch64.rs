@@ -2,7 +2,7 @@
 // OPT: Use a super optimizer to find optimal sequences for add, mul, neg, sub,
 // and shift.
 use super::MODULUS;
-use std::arch::aarch64::*;
+use core::arch::aarch64::*;
 
 #[inline(always)]
 #[must_use]
@@ -219,7 +219,7 @@ mod inst {
 }
 
 fn pair(a: u64, b: u64) -> uint64x2_t {
-    unsafe { std::mem::transmute([a, b]) }
+    unsafe { core::mem::transmute([a, b]) }
 }
 
 #[cfg(test)]
@@ -241,10 +241,10 @@ mod test {
                 generic::add(a[0], b[0]),
                 generic::add(a[1], b[1]),
             ];
-            let a = unsafe { std::mem::transmute(a) };
-            let b = unsafe { std::mem::transmute(b) };
+            let a = unsafe { core::mem::transmute(a) };
+            let b = unsafe { core::mem::transmute(b) };
             let value = vadd(a, b);
-            let value: [u64;2] = unsafe { std::mem::transmute(value) };
+            let value: [u64;2] = unsafe { core::mem::transmute(value) };
             assert_eq!(value, expected);
         });
     }
@@ -260,10 +260,10 @@ mod test {
                 generic::sub(a[0], b[0]),
                 generic::sub(a[1], b[1]),
             ];
-            let a = unsafe { std::mem::transmute(a) };
-            let b = unsafe { std::mem::transmute(b) };
+            let a = unsafe { core::mem::transmute(a) };
+            let b = unsafe { core::mem::transmute(b) };
             let value = vsub(a, b);
-            let value: [u64;2] = unsafe { std::mem::transmute(value) };
+            let value: [u64;2] = unsafe { core::mem::transmute(value) };
             assert_eq!(value, expected);
         });
     }
@@ -277,10 +277,10 @@ mod test {
                 generic::mont_reduce_128((x0[0] as u128) + ((x1[0] as u128) << 64)),
                 generic::mont_reduce_128((x0[1] as u128) + ((x1[1] as u128) << 64)),
             ];
-            let x0 = unsafe { std::mem::transmute(x0) };
-            let x1 = unsafe { std::mem::transmute(x1) };
+            let x0 = unsafe { core::mem::transmute(x0) };
+            let x1 = unsafe { core::mem::transmute(x1) };
             let value = mont_reduce_128(x0, x1);
-            let value: [u64;2] = unsafe { std::mem::transmute(value) };
+            let value: [u64;2] = unsafe { core::mem::transmute(value) };
             assert_eq!(value, expected);
         });
     }
@@ -294,10 +294,10 @@ mod test {
                 generic::mont_reduce_128((x0[0] as u128) + ((x1[0] as u128) << 64)),
                 generic::mont_reduce_128((x0[1] as u128) + ((x1[1] as u128) << 64)),
             ];
-            let x0 = unsafe { std::mem::transmute(x0) };
-            let x1 = unsafe { std::mem::transmute(x1) };
+            let x0 = unsafe { core::mem::transmute(x0) };
+            let x1 = unsafe { core::mem::transmute(x1) };
             let value = mont_reduce_128_asm(x0, x1);
-            let value: [u64;2] = unsafe { std::mem::transmute(value) };
+            let value: [u64;2] = unsafe { core::mem::transmute(value) };
             assert_eq!(value, expected);
         });
     }
@@ -326,7 +326,7 @@ pub mod bench {
         a.iter_mut().for_each(|n| {
             *n = pair(rng.gen::<u64>() % MODULUS, rng.gen::<u64>() % MODULUS);
         });
-        unsafe { std::mem::transmute(a) }
+        unsafe { core::mem::transmute(a) }
     }
 
     /// Benchmark a binary (two-input) function.
@@ -0,0 +1,26 @@
+#![cfg(feature = "counters")]
+//! Per-thread count of field multiplications, for verifying operation counts
+//! in tests. Requires the `counters` feature, which slows down every
+//! multiplication.
+
+use std::cell::Cell;
+
+thread_local! {
+    static MUL_COUNT: Cell<u64> = const { Cell::new(0) };
+}
+
+#[inline(always)]
+pub(crate) fn count_mul() {
+    MUL_COUNT.with(|count| count.set(count.get() + 1));
+}
+
+/// Number of field multiplications on this thread since the last [`reset`].
+#[must_use]
+pub fn mul_count() -> u64 {
+    MUL_COUNT.with(Cell::get)
+}
+
+/// Reset the multiplication count of this thread.
+pub fn reset() {
+    MUL_COUNT.with(|count| count.set(0));
+}
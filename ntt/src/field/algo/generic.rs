@@ -44,12 +44,16 @@ pub fn sub(a: u64, b: u64) -> u64 {
 pub fn mul(a: u64, b: u64) -> u64 {
     debug_assert!(a < MODULUS);
     debug_assert!(b < MODULUS);
+    #[cfg(feature = "counters")]
+    super::counters::count_mul();
     reduce_128((a as u128) * (b as u128))
 }
 
 #[inline(always)]
 #[must_use]
 pub fn mont_mul(a: u64, b: u64) -> u64 {
+    #[cfg(feature = "counters")]
+    super::counters::count_mul();
     mont_reduce_128((a as u128) * (b as u128))
 }
 
@@ -57,7 +61,7 @@ pub fn mont_mul(a: u64, b: u64) -> u64 {
 #[inline(always)]
 #[must_use]
 pub const fn reduce_64(mut n: u64) -> u64 {
-    if n > MODULUS {
+    if n >= MODULUS {
         n -= MODULUS;
     }
     n
@@ -70,11 +74,13 @@ pub fn reduce_128(n: u128) -> u64 {
     reduce_159(n as u64, (n >> 64) as u32, (n >> 96) as u64)
 }
 
-/// Reduce a 159 bit number
+/// Reduce a 159 bit number `low + mid · 2^64 + high · 2^96`.
+///
 /// See <https://cp4space.hatsya.com/2021/09/01/an-efficient-prime-for-number-theoretic-transforms/>
 /// See <https://github.com/mir-protocol/plonky2/blob/3a6d693f3ffe5aa1636e0066a4ea4885a10b5cdf/field/src/goldilocks_field.rs#L340-L356>
 #[inline(always)]
-fn reduce_159(low: u64, mid: u32, high: u64) -> u64 {
+#[must_use]
+pub fn reduce_159(low: u64, mid: u32, high: u64) -> u64 {
     debug_assert!(high <= u64::MAX >> 1);
     let (mut low2, carry) = low.overflowing_sub(high);
     if carry {
@@ -207,6 +213,96 @@ pub fn root_384(a: u64, i: u64) -> u64 {
     }
 }
 
+/// Apply `v = a · v + b` to Montgomery form values.
+#[inline]
+pub fn mont_affine(values: &mut [u64], a: u64, b: u64) {
+    for value in values {
+        *value = add(mont_mul(a, *value), b);
+    }
+}
+
+/// Pointwise Montgomery product `a[i] = a[i] · b[i]`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn mont_mul_assign(a: &mut [u64], b: &[u64]) {
+    assert_eq!(a.len(), b.len());
+    for (a, &b) in a.iter_mut().zip(b) {
+        *a = mont_mul(*a, b);
+    }
+}
+
+/// Pointwise sum `a[i] = a[i] + b[i]`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn add_assign(a: &mut [u64], b: &[u64]) {
+    assert_eq!(a.len(), b.len());
+    for (a, &b) in a.iter_mut().zip(b) {
+        *a = add(*a, b);
+    }
+}
+
+/// Pointwise difference `a[i] = a[i] - b[i]`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn sub_assign(a: &mut [u64], b: &[u64]) {
+    assert_eq!(a.len(), b.len());
+    for (a, &b) in a.iter_mut().zip(b) {
+        *a = sub(*a, b);
+    }
+}
+
+/// Radix-2 butterflies `(l, h) ← (l + w·h, l - w·h)` with `w` the Montgomery
+/// twiddle for each index.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+pub fn butterflies(low: &mut [u64], high: &mut [u64], twiddles: &[u64]) {
+    assert_eq!(low.len(), high.len());
+    assert_eq!(low.len(), twiddles.len());
+    for ((a, b), &w) in low.iter_mut().zip(high).zip(twiddles) {
+        let t = mont_mul(*b, w);
+        *b = sub(*a, t);
+        *a = add(*a, t);
+    }
+}
+
+/// Two fused radix-2 stages on a `chunk` of length `4h`: spans `h` with
+/// twiddles `w1` of length `h` and span `2h` with twiddles `w2` of length
+/// `2h`. Each value is loaded and stored once for both stages.
+///
+/// # Panics
+///
+/// Panics if the lengths do not match.
+#[inline]
+pub fn butterflies_4(chunk: &mut [u64], w1: &[u64], w2: &[u64]) {
+    let h = w1.len();
+    assert_eq!(chunk.len(), 4 * h);
+    assert_eq!(w2.len(), 2 * h);
+    let (x01, x23) = chunk.split_at_mut(2 * h);
+    let (x0, x1) = x01.split_at_mut(h);
+    let (x2, x3) = x23.split_at_mut(h);
+    let (w2, w3) = w2.split_at(h);
+    for j in 0..h {
+        let t1 = mont_mul(x1[j], w1[j]);
+        let (b0, b1) = (add(x0[j], t1), sub(x0[j], t1));
+        let t3 = mont_mul(x3[j], w1[j]);
+        let (b2, b3) = (add(x2[j], t3), sub(x2[j], t3));
+        let u = mont_mul(b2, w2[j]);
+        let v = mont_mul(b3, w3[j]);
+        x0[j] = add(b0, u);
+        x2[j] = sub(b0, u);
+        x1[j] = add(b1, v);
+        x3[j] = sub(b1, v);
+    }
+}
+
 #[inline(always)]
 #[must_use]
 pub fn inv(a: u64) -> u64 {
@@ -275,6 +371,22 @@ fn inv_ebgcd(a: u64) -> u64 {
 /// Returns 0 for 0.
 #[allow(dead_code)]
 fn inv_addchain(a: u64) -> u64 {
+    pow_p_minus_2(a, mul)
+}
+
+/// Modular inverse of a value in Montgomery form using an addition chain.
+///
+/// Runs in constant time: it is a fixed sequence of 72 branch-free Montgomery
+/// multiplications. Returns 0 for 0.
+#[inline]
+#[must_use]
+pub fn mont_inv_addchain(a: u64) -> u64 {
+    pow_p_minus_2(a, mont_mul)
+}
+
+/// Compute `a^(p - 2)` using the multiplication `mul`.
+#[inline(always)]
+fn pow_p_minus_2(a: u64, mul: impl Fn(u64, u64) -> u64) -> u64 {
     debug_assert!(a < MODULUS);
 
     // Invert using Fermat's little theorem.
@@ -1,9 +1,92 @@
 pub mod aarch64;
+pub mod counters;
 pub mod generic;
-
-pub use self::generic::{inv, mont_mul, mont_pow, mul, reduce_128, reduce_64, root_384, shift};
+pub mod simd;
+
+pub use self::generic::{
+    inv, mont_inv_addchain, mont_mul, mont_pow, mul, reduce_128, reduce_64, root_384, shift,
+};
+use crate::Field;
+use alloc::vec::Vec;
+use bytemuck::TransparentWrapper;
+#[cfg(feature = "std")]
+use rayon::prelude::*;
 // pub use self::aarch64::{add, sub};
 pub use self::generic::{add, sub};
+#[cfg(not(all(target_arch = "x86_64", feature = "std")))]
+pub use self::generic::{add_assign, butterflies, butterflies_4, mont_mul_assign, sub_assign};
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+pub use self::simd::{add_assign, butterflies, butterflies_4, mont_mul_assign, sub_assign};
+
+/// Chunk size for parallel slice operations.
+const PAR_CHUNK: usize = 1 << 14;
+
+/// Apply `v = a · v + b` to all values in a single pass, in parallel for large
+/// slices with `std`.
+pub fn affine(values: &mut [Field], a: Field, b: Field) {
+    let values = Field::peel_slice_mut(values);
+    let (a, b) = (Field::peel(a), Field::peel(b));
+    if !cfg!(feature = "std") || values.len() <= PAR_CHUNK {
+        generic::mont_affine(values, a, b);
+    } else {
+        #[cfg(feature = "std")]
+        values
+            .par_chunks_mut(PAR_CHUNK)
+            .for_each(|chunk| generic::mont_affine(chunk, a, b));
+    }
+}
+
+/// Pointwise product `a[i] *= b[i]`, using AVX-512 or AVX2 when available.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn mul_assign_slice(a: &mut [Field], b: &[Field]) {
+    assert_eq!(a.len(), b.len());
+    mont_mul_assign(Field::peel_slice_mut(a), Field::peel_slice(b));
+}
+
+/// Pointwise sum `a[i] += b[i]`, using AVX-512 or AVX2 when available.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn add_assign_slice(a: &mut [Field], b: &[Field]) {
+    assert_eq!(a.len(), b.len());
+    add_assign(Field::peel_slice_mut(a), Field::peel_slice(b));
+}
+
+/// Pointwise difference `a[i] -= b[i]`, using AVX-512 or AVX2 when available.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn sub_assign_slice(a: &mut [Field], b: &[Field]) {
+    assert_eq!(a.len(), b.len());
+    sub_assign(Field::peel_slice_mut(a), Field::peel_slice(b));
+}
+
+/// Replace each value by its inverse, leaving zeros as zero.
+///
+/// Uses Montgomery's trick: a single inversion of the product of all non-zero
+/// values and three multiplications per value.
+pub fn batch_inverse(values: &mut [Field]) {
+    let zero = Field::from(0);
+    let mut prefix = Vec::with_capacity(values.len());
+    let mut product = Field::from(1);
+    for &value in values.iter() {
+        prefix.push(product);
+        if value != zero {
+            product *= value;
+        }
+    }
+    let mut inverse = product.inv();
+    for (value, &prefix) in values.iter_mut().zip(&prefix).rev() {
+        if *value != zero {
+            (*value, inverse) = (inverse * prefix, inverse * *value);
+        }
+    }
+}
 
 /// p = φ² - φ + 1 = 2⁶⁴ - 2³² + 1
 pub const MODULUS: u64 = 0xffff_ffff_0000_0001;
@@ -24,6 +107,77 @@ mod test {
     use super::*;
     use proptest::{prop_assume, proptest};
 
+    #[test]
+    fn test_affine() {
+        proptest!(|(values: Vec<u64>, a: u64, b: u64)| {
+            let values = values.into_iter().map(Field::from).collect::<Vec<_>>();
+            let (a, b) = (Field::from(a), Field::from(b));
+            let expected = values.iter().map(|&v| a * v + b).collect::<Vec<_>>();
+            let mut result = values;
+            affine(&mut result, a, b);
+            assert_eq!(result, expected);
+        });
+        let mut values = vec![Field::from(3); 3 * PAR_CHUNK + 1];
+        affine(&mut values, Field::from(2), Field::from(1));
+        assert!(values.iter().all(|&v| v == Field::from(7)));
+    }
+
+    #[test]
+    fn test_mul_assign_slice() {
+        proptest!(|(pairs: Vec<(Field, Field)>)| {
+            let (mut a, b): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+            let expected = a.iter().zip(&b).map(|(&a, &b)| a * b).collect::<Vec<_>>();
+            mul_assign_slice(&mut a, &b);
+            assert_eq!(a, expected);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "left == right")]
+    fn test_mul_assign_slice_length() {
+        mul_assign_slice(&mut [Field::from(1)], &[]);
+    }
+
+    #[test]
+    fn test_add_sub_assign_slice() {
+        // Sums and differences straddling the modulus.
+        let p = Field::MODULUS;
+        let edge = [0, 1, 2, p / 2, p - 2, p - 1].map(Field::from);
+        let (a, b): (Vec<_>, Vec<_>) = edge
+            .iter()
+            .flat_map(|&a| edge.iter().map(move |&b| (a, b)))
+            .unzip();
+        let mut sum = a.clone();
+        let mut difference = a.clone();
+        add_assign_slice(&mut sum, &b);
+        sub_assign_slice(&mut difference, &b);
+        for i in 0..a.len() {
+            assert_eq!(sum[i], a[i] + b[i]);
+            assert_eq!(difference[i], a[i] - b[i]);
+        }
+        proptest!(|(pairs: Vec<(Field, Field)>)| {
+            let (a, b): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+            let mut sum = a.clone();
+            add_assign_slice(&mut sum, &b);
+            assert_eq!(sum, a.iter().zip(&b).map(|(&a, &b)| a + b).collect::<Vec<_>>());
+            let mut difference = a.clone();
+            sub_assign_slice(&mut difference, &b);
+            assert_eq!(difference, a.iter().zip(&b).map(|(&a, &b)| a - b).collect::<Vec<_>>());
+        });
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        proptest!(|(values: Vec<Field>)| {
+            let mut values = values;
+            values.extend([Field::from(0), Field::from(1)]);
+            let expected = values.iter().map(|v| v.inv()).collect::<Vec<_>>();
+            batch_inverse(&mut values);
+            assert_eq!(values, expected);
+        });
+        batch_inverse(&mut []);
+    }
+
     #[test]
     fn test_add_naive() {
         proptest!(|(a: u64, b: u64)| {
@@ -236,8 +390,43 @@ pub mod bench {
         bench_unary(criterion, "shift/32", |a| shift(a, 32));
         bench_unary(criterion, "shift/64", |a| shift(a, 64));
 
+        bench_affine(criterion, 1 << 22);
+        bench_batch_inverse(criterion, 1024);
+
         #[cfg(target_arch = "aarch64")]
         aarch64::bench::group(criterion);
+        #[cfg(target_arch = "x86_64")]
+        simd::bench::group(criterion);
+    }
+
+    /// Compare the fused affine map with separate scale and add passes.
+    fn bench_affine(criterion: &mut Criterion, size: usize) {
+        let mut values: Vec<Field> = crate::bench::rand_vec(size);
+        let (a, b) = (Field::from(rand()), Field::from(rand()));
+        let mut group = criterion.benchmark_group("field/affine");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("separate", size), |bencher| {
+            bencher.iter(|| {
+                values.par_iter_mut().for_each(|v| *v *= a);
+                values.par_iter_mut().for_each(|v| *v += b);
+            });
+        });
+        group.bench_function(BenchmarkId::new("affine", size), |bencher| {
+            bencher.iter(|| affine(&mut values, a, b));
+        });
+    }
+
+    /// Montgomery's trick versus inverting each value.
+    fn bench_batch_inverse(criterion: &mut Criterion, size: usize) {
+        let mut values: Vec<Field> = crate::bench::rand_vec(size);
+        let mut group = criterion.benchmark_group("field/batch_inverse");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("inv", size), |bencher| {
+            bencher.iter(|| values.iter_mut().for_each(|v| *v = v.inv()));
+        });
+        group.bench_function(BenchmarkId::new("batch", size), |bencher| {
+            bencher.iter(|| batch_inverse(&mut values));
+        });
     }
 
     #[must_use]
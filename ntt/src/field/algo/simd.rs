@@ -0,0 +1,744 @@
+#![cfg(all(target_arch = "x86_64", feature = "std"))]
+//! Batched Montgomery multiplication and NTT butterflies using AVX-512 and
+//! AVX2. The backend is detected once at runtime, which requires `std`.
+//!
+//! All kernels agree bit for bit with [`generic`]: they perform the same
+//! reduction steps lane by lane. AVX2 only has signed 64 bit comparison, so
+//! unsigned carries are detected by flipping the sign bits. AVX-512 has
+//! unsigned comparisons into mask registers.
+
+use super::{generic, MODULUS};
+use core::arch::x86_64::{
+    __m256i, __m512i, _mm256_add_epi64, _mm256_and_si256, _mm256_cmpgt_epi64, _mm256_loadu_si256,
+    _mm256_mul_epu32, _mm256_or_si256, _mm256_set1_epi64x, _mm256_slli_epi64, _mm256_srli_epi64,
+    _mm256_storeu_si256, _mm256_sub_epi64, _mm256_xor_si256, _mm512_add_epi64, _mm512_and_si512,
+    _mm512_cmplt_epu64_mask, _mm512_loadu_si512, _mm512_mask_sub_epi64, _mm512_mul_epu32,
+    _mm512_or_si512, _mm512_set1_epi64, _mm512_slli_epi64, _mm512_srli_epi64, _mm512_storeu_si512,
+    _mm512_sub_epi64,
+};
+use std::sync::OnceLock;
+
+/// Vector instruction set used for the kernels.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Backend {
+    Avx512,
+    Avx2,
+    Generic,
+}
+
+impl Backend {
+    /// The best backend supported by this CPU, detected on first use.
+    #[must_use]
+    pub fn detect() -> Self {
+        static BACKEND: OnceLock<Backend> = OnceLock::new();
+        *BACKEND.get_or_init(|| {
+            if is_x86_feature_detected!("avx512f") {
+                Self::Avx512
+            } else if is_x86_feature_detected!("avx2") {
+                Self::Avx2
+            } else {
+                Self::Generic
+            }
+        })
+    }
+
+    /// Whether this CPU supports the backend.
+    #[must_use]
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Avx512 => is_x86_feature_detected!("avx512f"),
+            Self::Avx2 => is_x86_feature_detected!("avx2"),
+            Self::Generic => true,
+        }
+    }
+}
+
+/// Pointwise Montgomery product `a[i] = a[i] · b[i]`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn mont_mul_assign(a: &mut [u64], b: &[u64]) {
+    assert_eq!(a.len(), b.len());
+    match Backend::detect() {
+        // Safety: the backend is detected.
+        Backend::Avx512 => unsafe { mont_mul_assign_avx512(a, b) },
+        Backend::Avx2 => unsafe { mont_mul_assign_avx2(a, b) },
+        Backend::Generic => generic::mont_mul_assign(a, b),
+    }
+}
+
+/// Pointwise sum `a[i] = a[i] + b[i]`, i.e. [`generic::add_assign`].
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn add_assign(a: &mut [u64], b: &[u64]) {
+    assert_eq!(a.len(), b.len());
+    match Backend::detect() {
+        // Safety: the backend is detected.
+        Backend::Avx512 => unsafe { add_assign_avx512(a, b) },
+        Backend::Avx2 => unsafe { add_assign_avx2(a, b) },
+        Backend::Generic => generic::add_assign(a, b),
+    }
+}
+
+/// Pointwise difference `a[i] = a[i] - b[i]`, i.e. [`generic::sub_assign`].
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn sub_assign(a: &mut [u64], b: &[u64]) {
+    assert_eq!(a.len(), b.len());
+    match Backend::detect() {
+        // Safety: the backend is detected.
+        Backend::Avx512 => unsafe { sub_assign_avx512(a, b) },
+        Backend::Avx2 => unsafe { sub_assign_avx2(a, b) },
+        Backend::Generic => generic::sub_assign(a, b),
+    }
+}
+
+/// Radix-2 butterflies `(l, h) ← (l + w·h, l - w·h)` with `w` the Montgomery
+/// twiddle for each index, i.e. [`generic::butterflies`].
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+pub fn butterflies(low: &mut [u64], high: &mut [u64], twiddles: &[u64]) {
+    assert_eq!(low.len(), high.len());
+    assert_eq!(low.len(), twiddles.len());
+    match Backend::detect() {
+        // Safety: the backend is detected.
+        Backend::Avx512 => unsafe { butterflies_avx512(low, high, twiddles) },
+        Backend::Avx2 => unsafe { butterflies_avx2(low, high, twiddles) },
+        Backend::Generic => generic::butterflies(low, high, twiddles),
+    }
+}
+
+/// Two fused radix-2 stages, i.e. [`generic::butterflies_4`].
+///
+/// # Panics
+///
+/// Panics if the lengths do not match.
+pub fn butterflies_4(chunk: &mut [u64], w1: &[u64], w2: &[u64]) {
+    assert_eq!(chunk.len(), 4 * w1.len());
+    assert_eq!(w2.len(), 2 * w1.len());
+    match Backend::detect() {
+        // Safety: the backend is detected, and AVX-512F implies AVX2.
+        Backend::Avx512 if w1.len() >= 8 => unsafe { butterflies_4_avx512(chunk, w1, w2) },
+        Backend::Avx512 | Backend::Avx2 => unsafe { butterflies_4_avx2(chunk, w1, w2) },
+        Backend::Generic => generic::butterflies_4(chunk, w1, w2),
+    }
+}
+
+/// [`mont_mul_assign`] with a given backend, for testing and benchmarks.
+#[cfg(any(test, feature = "bench"))]
+fn mont_mul_assign_with(backend: Backend, a: &mut [u64], b: &[u64]) {
+    assert_eq!(a.len(), b.len());
+    assert!(backend.is_supported());
+    match backend {
+        // Safety: feature support is asserted.
+        Backend::Avx512 => unsafe { mont_mul_assign_avx512(a, b) },
+        Backend::Avx2 => unsafe { mont_mul_assign_avx2(a, b) },
+        Backend::Generic => generic::mont_mul_assign(a, b),
+    }
+}
+
+/// [`add_assign`] with a given backend, for testing and benchmarks.
+#[cfg(any(test, feature = "bench"))]
+fn add_assign_with(backend: Backend, a: &mut [u64], b: &[u64]) {
+    assert_eq!(a.len(), b.len());
+    assert!(backend.is_supported());
+    match backend {
+        // Safety: feature support is asserted.
+        Backend::Avx512 => unsafe { add_assign_avx512(a, b) },
+        Backend::Avx2 => unsafe { add_assign_avx2(a, b) },
+        Backend::Generic => generic::add_assign(a, b),
+    }
+}
+
+/// [`sub_assign`] with a given backend, for testing and benchmarks.
+#[cfg(any(test, feature = "bench"))]
+fn sub_assign_with(backend: Backend, a: &mut [u64], b: &[u64]) {
+    assert_eq!(a.len(), b.len());
+    assert!(backend.is_supported());
+    match backend {
+        // Safety: feature support is asserted.
+        Backend::Avx512 => unsafe { sub_assign_avx512(a, b) },
+        Backend::Avx2 => unsafe { sub_assign_avx2(a, b) },
+        Backend::Generic => generic::sub_assign(a, b),
+    }
+}
+
+/// [`butterflies`] with a given backend, for testing and benchmarks.
+#[cfg(any(test, feature = "bench"))]
+fn butterflies_with(backend: Backend, low: &mut [u64], high: &mut [u64], twiddles: &[u64]) {
+    assert_eq!(low.len(), high.len());
+    assert_eq!(low.len(), twiddles.len());
+    assert!(backend.is_supported());
+    match backend {
+        // Safety: feature support is asserted.
+        Backend::Avx512 => unsafe { butterflies_avx512(low, high, twiddles) },
+        Backend::Avx2 => unsafe { butterflies_avx2(low, high, twiddles) },
+        Backend::Generic => generic::butterflies(low, high, twiddles),
+    }
+}
+
+/// [`butterflies_4`] with a given backend, for testing and benchmarks.
+#[cfg(any(test, feature = "bench"))]
+fn butterflies_4_with(backend: Backend, chunk: &mut [u64], w1: &[u64], w2: &[u64]) {
+    assert_eq!(chunk.len(), 4 * w1.len());
+    assert_eq!(w2.len(), 2 * w1.len());
+    assert!(backend.is_supported());
+    match backend {
+        // Safety: feature support is asserted.
+        Backend::Avx512 => unsafe { butterflies_4_avx512(chunk, w1, w2) },
+        Backend::Avx2 => unsafe { butterflies_4_avx2(chunk, w1, w2) },
+        Backend::Generic => generic::butterflies_4(chunk, w1, w2),
+    }
+}
+
+/// # Safety
+///
+/// Requires AVX2.
+#[target_feature(enable = "avx2")]
+unsafe fn mont_mul_assign_avx2(a: &mut [u64], b: &[u64]) {
+    let mut a_chunks = a.chunks_exact_mut(4);
+    let mut b_chunks = b.chunks_exact(4);
+    for (a, b) in (&mut a_chunks).zip(&mut b_chunks) {
+        unsafe {
+            let x = _mm256_loadu_si256(a.as_ptr().cast());
+            let y = _mm256_loadu_si256(b.as_ptr().cast());
+            _mm256_storeu_si256(a.as_mut_ptr().cast(), mont_mul_avx2(x, y));
+        }
+    }
+    generic::mont_mul_assign(a_chunks.into_remainder(), b_chunks.remainder());
+}
+
+/// # Safety
+///
+/// Requires AVX2.
+#[target_feature(enable = "avx2")]
+unsafe fn add_assign_avx2(a: &mut [u64], b: &[u64]) {
+    let mut a_chunks = a.chunks_exact_mut(4);
+    let mut b_chunks = b.chunks_exact(4);
+    for (a, b) in (&mut a_chunks).zip(&mut b_chunks) {
+        unsafe {
+            let x = _mm256_loadu_si256(a.as_ptr().cast());
+            let y = _mm256_loadu_si256(b.as_ptr().cast());
+            _mm256_storeu_si256(a.as_mut_ptr().cast(), add_avx2(x, y));
+        }
+    }
+    generic::add_assign(a_chunks.into_remainder(), b_chunks.remainder());
+}
+
+/// # Safety
+///
+/// Requires AVX2.
+#[target_feature(enable = "avx2")]
+unsafe fn sub_assign_avx2(a: &mut [u64], b: &[u64]) {
+    let mut a_chunks = a.chunks_exact_mut(4);
+    let mut b_chunks = b.chunks_exact(4);
+    for (a, b) in (&mut a_chunks).zip(&mut b_chunks) {
+        unsafe {
+            let x = _mm256_loadu_si256(a.as_ptr().cast());
+            let y = _mm256_loadu_si256(b.as_ptr().cast());
+            _mm256_storeu_si256(a.as_mut_ptr().cast(), sub_avx2(x, y));
+        }
+    }
+    generic::sub_assign(a_chunks.into_remainder(), b_chunks.remainder());
+}
+
+/// # Safety
+///
+/// Requires AVX2.
+#[target_feature(enable = "avx2")]
+unsafe fn butterflies_avx2(low: &mut [u64], high: &mut [u64], twiddles: &[u64]) {
+    let mut low_chunks = low.chunks_exact_mut(4);
+    let mut high_chunks = high.chunks_exact_mut(4);
+    let mut twiddle_chunks = twiddles.chunks_exact(4);
+    for ((low, high), twiddles) in (&mut low_chunks)
+        .zip(&mut high_chunks)
+        .zip(&mut twiddle_chunks)
+    {
+        unsafe {
+            let a = _mm256_loadu_si256(low.as_ptr().cast());
+            let b = _mm256_loadu_si256(high.as_ptr().cast());
+            let w = _mm256_loadu_si256(twiddles.as_ptr().cast());
+            let t = mont_mul_avx2(b, w);
+            _mm256_storeu_si256(high.as_mut_ptr().cast(), sub_avx2(a, t));
+            _mm256_storeu_si256(low.as_mut_ptr().cast(), add_avx2(a, t));
+        }
+    }
+    generic::butterflies(
+        low_chunks.into_remainder(),
+        high_chunks.into_remainder(),
+        twiddle_chunks.remainder(),
+    );
+}
+
+/// # Safety
+///
+/// Requires AVX2.
+#[target_feature(enable = "avx2")]
+unsafe fn butterflies_4_avx2(chunk: &mut [u64], w1: &[u64], w2: &[u64]) {
+    let h = w1.len();
+    let full = h - h % 4;
+    let x = chunk.as_mut_ptr();
+    for j in (0..full).step_by(4) {
+        // Safety: `j + 4 <= h` and the chunk has length `4h`.
+        unsafe {
+            let a0 = _mm256_loadu_si256(x.add(j).cast());
+            let a1 = _mm256_loadu_si256(x.add(h + j).cast());
+            let a2 = _mm256_loadu_si256(x.add(2 * h + j).cast());
+            let a3 = _mm256_loadu_si256(x.add(3 * h + j).cast());
+            let w1 = _mm256_loadu_si256(w1.as_ptr().add(j).cast());
+            let w2a = _mm256_loadu_si256(w2.as_ptr().add(j).cast());
+            let w2b = _mm256_loadu_si256(w2.as_ptr().add(h + j).cast());
+            let t1 = mont_mul_avx2(a1, w1);
+            let (b0, b1) = (add_avx2(a0, t1), sub_avx2(a0, t1));
+            let t3 = mont_mul_avx2(a3, w1);
+            let (b2, b3) = (add_avx2(a2, t3), sub_avx2(a2, t3));
+            let u = mont_mul_avx2(b2, w2a);
+            let v = mont_mul_avx2(b3, w2b);
+            _mm256_storeu_si256(x.add(j).cast(), add_avx2(b0, u));
+            _mm256_storeu_si256(x.add(2 * h + j).cast(), sub_avx2(b0, u));
+            _mm256_storeu_si256(x.add(h + j).cast(), add_avx2(b1, v));
+            _mm256_storeu_si256(x.add(3 * h + j).cast(), sub_avx2(b1, v));
+        }
+    }
+    butterflies_4_tail(chunk, w1, w2, full);
+}
+
+/// The scalar butterflies for `j >= from` of [`butterflies_4`].
+fn butterflies_4_tail(chunk: &mut [u64], w1: &[u64], w2: &[u64], from: usize) {
+    let h = w1.len();
+    for j in from..h {
+        let mut quad = [chunk[j], chunk[h + j], chunk[2 * h + j], chunk[3 * h + j]];
+        generic::butterflies_4(&mut quad, &w1[j..=j], &[w2[j], w2[h + j]]);
+        for (i, value) in quad.into_iter().enumerate() {
+            chunk[i * h + j] = value;
+        }
+    }
+}
+
+/// Four lanes of `a < b` as all-ones masks.
+#[target_feature(enable = "avx2")]
+#[inline]
+fn less_avx2(a: __m256i, b: __m256i) -> __m256i {
+    let sign = _mm256_set1_epi64x(i64::MIN);
+    _mm256_cmpgt_epi64(_mm256_xor_si256(b, sign), _mm256_xor_si256(a, sign))
+}
+
+/// Four lanes of [`generic::add`].
+#[target_feature(enable = "avx2")]
+#[inline]
+fn add_avx2(a: __m256i, b: __m256i) -> __m256i {
+    let b = _mm256_sub_epi64(_mm256_set1_epi64x(MODULUS.cast_signed()), b);
+    let r = _mm256_sub_epi64(a, b);
+    _mm256_sub_epi64(r, _mm256_srli_epi64::<32>(less_avx2(a, b)))
+}
+
+/// Four lanes of [`generic::sub`].
+#[target_feature(enable = "avx2")]
+#[inline]
+fn sub_avx2(a: __m256i, b: __m256i) -> __m256i {
+    let r = _mm256_sub_epi64(a, b);
+    _mm256_sub_epi64(r, _mm256_srli_epi64::<32>(less_avx2(a, b)))
+}
+
+/// Four lanes of [`generic::mont_mul`].
+///
+/// The 128 bit products are assembled from four 32 × 32 bit products, then
+/// reduced as in [`generic::mont_reduce_128`].
+#[target_feature(enable = "avx2")]
+#[inline]
+fn mont_mul_avx2(a: __m256i, b: __m256i) -> __m256i {
+    let low = _mm256_set1_epi64x(0xffff_ffff);
+
+    // x1 · 2^64 + x0 = a · b
+    let a_hi = _mm256_srli_epi64::<32>(a);
+    let b_hi = _mm256_srli_epi64::<32>(b);
+    let ll = _mm256_mul_epu32(a, b);
+    let lh = _mm256_mul_epu32(a, b_hi);
+    let hl = _mm256_mul_epu32(a_hi, b);
+    let hh = _mm256_mul_epu32(a_hi, b_hi);
+    let mid = _mm256_add_epi64(
+        _mm256_srli_epi64::<32>(ll),
+        _mm256_add_epi64(_mm256_and_si256(lh, low), _mm256_and_si256(hl, low)),
+    );
+    let x0 = _mm256_or_si256(_mm256_and_si256(ll, low), _mm256_slli_epi64::<32>(mid));
+    let x1 = _mm256_add_epi64(
+        _mm256_add_epi64(hh, _mm256_srli_epi64::<32>(mid)),
+        _mm256_add_epi64(_mm256_srli_epi64::<32>(lh), _mm256_srli_epi64::<32>(hl)),
+    );
+
+    // Montgomery reduction, with carries as all-ones masks.
+    let t = _mm256_add_epi64(x0, _mm256_slli_epi64::<32>(x0));
+    let carry = less_avx2(t, x0);
+    let t = _mm256_add_epi64(_mm256_sub_epi64(t, _mm256_srli_epi64::<32>(t)), carry);
+    let r = _mm256_sub_epi64(x1, t);
+    _mm256_sub_epi64(r, _mm256_srli_epi64::<32>(less_avx2(x1, t)))
+}
+
+/// # Safety
+///
+/// Requires AVX-512F.
+#[target_feature(enable = "avx512f")]
+unsafe fn mont_mul_assign_avx512(a: &mut [u64], b: &[u64]) {
+    let mut a_chunks = a.chunks_exact_mut(8);
+    let mut b_chunks = b.chunks_exact(8);
+    for (a, b) in (&mut a_chunks).zip(&mut b_chunks) {
+        unsafe {
+            let x = _mm512_loadu_si512(a.as_ptr().cast());
+            let y = _mm512_loadu_si512(b.as_ptr().cast());
+            _mm512_storeu_si512(a.as_mut_ptr().cast(), mont_mul_avx512(x, y));
+        }
+    }
+    generic::mont_mul_assign(a_chunks.into_remainder(), b_chunks.remainder());
+}
+
+/// # Safety
+///
+/// Requires AVX-512F.
+#[target_feature(enable = "avx512f")]
+unsafe fn add_assign_avx512(a: &mut [u64], b: &[u64]) {
+    let mut a_chunks = a.chunks_exact_mut(8);
+    let mut b_chunks = b.chunks_exact(8);
+    for (a, b) in (&mut a_chunks).zip(&mut b_chunks) {
+        unsafe {
+            let x = _mm512_loadu_si512(a.as_ptr().cast());
+            let y = _mm512_loadu_si512(b.as_ptr().cast());
+            _mm512_storeu_si512(a.as_mut_ptr().cast(), add_avx512(x, y));
+        }
+    }
+    generic::add_assign(a_chunks.into_remainder(), b_chunks.remainder());
+}
+
+/// # Safety
+///
+/// Requires AVX-512F.
+#[target_feature(enable = "avx512f")]
+unsafe fn sub_assign_avx512(a: &mut [u64], b: &[u64]) {
+    let mut a_chunks = a.chunks_exact_mut(8);
+    let mut b_chunks = b.chunks_exact(8);
+    for (a, b) in (&mut a_chunks).zip(&mut b_chunks) {
+        unsafe {
+            let x = _mm512_loadu_si512(a.as_ptr().cast());
+            let y = _mm512_loadu_si512(b.as_ptr().cast());
+            _mm512_storeu_si512(a.as_mut_ptr().cast(), sub_avx512(x, y));
+        }
+    }
+    generic::sub_assign(a_chunks.into_remainder(), b_chunks.remainder());
+}
+
+/// # Safety
+///
+/// Requires AVX-512F.
+#[target_feature(enable = "avx512f")]
+unsafe fn butterflies_avx512(low: &mut [u64], high: &mut [u64], twiddles: &[u64]) {
+    let mut low_chunks = low.chunks_exact_mut(8);
+    let mut high_chunks = high.chunks_exact_mut(8);
+    let mut twiddle_chunks = twiddles.chunks_exact(8);
+    for ((low, high), twiddles) in (&mut low_chunks)
+        .zip(&mut high_chunks)
+        .zip(&mut twiddle_chunks)
+    {
+        unsafe {
+            let a = _mm512_loadu_si512(low.as_ptr().cast());
+            let b = _mm512_loadu_si512(high.as_ptr().cast());
+            let w = _mm512_loadu_si512(twiddles.as_ptr().cast());
+            let t = mont_mul_avx512(b, w);
+            _mm512_storeu_si512(high.as_mut_ptr().cast(), sub_avx512(a, t));
+            _mm512_storeu_si512(low.as_mut_ptr().cast(), add_avx512(a, t));
+        }
+    }
+    generic::butterflies(
+        low_chunks.into_remainder(),
+        high_chunks.into_remainder(),
+        twiddle_chunks.remainder(),
+    );
+}
+
+/// # Safety
+///
+/// Requires AVX-512F.
+#[target_feature(enable = "avx512f")]
+unsafe fn butterflies_4_avx512(chunk: &mut [u64], w1: &[u64], w2: &[u64]) {
+    let h = w1.len();
+    let full = h - h % 8;
+    let x = chunk.as_mut_ptr();
+    for j in (0..full).step_by(8) {
+        // Safety: `j + 8 <= h` and the chunk has length `4h`.
+        unsafe {
+            let a0 = _mm512_loadu_si512(x.add(j).cast());
+            let a1 = _mm512_loadu_si512(x.add(h + j).cast());
+            let a2 = _mm512_loadu_si512(x.add(2 * h + j).cast());
+            let a3 = _mm512_loadu_si512(x.add(3 * h + j).cast());
+            let w1 = _mm512_loadu_si512(w1.as_ptr().add(j).cast());
+            let w2a = _mm512_loadu_si512(w2.as_ptr().add(j).cast());
+            let w2b = _mm512_loadu_si512(w2.as_ptr().add(h + j).cast());
+            let t1 = mont_mul_avx512(a1, w1);
+            let (b0, b1) = (add_avx512(a0, t1), sub_avx512(a0, t1));
+            let t3 = mont_mul_avx512(a3, w1);
+            let (b2, b3) = (add_avx512(a2, t3), sub_avx512(a2, t3));
+            let u = mont_mul_avx512(b2, w2a);
+            let v = mont_mul_avx512(b3, w2b);
+            _mm512_storeu_si512(x.add(j).cast(), add_avx512(b0, u));
+            _mm512_storeu_si512(x.add(2 * h + j).cast(), sub_avx512(b0, u));
+            _mm512_storeu_si512(x.add(h + j).cast(), add_avx512(b1, v));
+            _mm512_storeu_si512(x.add(3 * h + j).cast(), sub_avx512(b1, v));
+        }
+    }
+    butterflies_4_tail(chunk, w1, w2, full);
+}
+
+/// Eight lanes of [`generic::add`].
+#[target_feature(enable = "avx512f")]
+#[inline]
+fn add_avx512(a: __m512i, b: __m512i) -> __m512i {
+    let b = _mm512_sub_epi64(_mm512_set1_epi64(MODULUS.cast_signed()), b);
+    let r = _mm512_sub_epi64(a, b);
+    let borrow = _mm512_cmplt_epu64_mask(a, b);
+    _mm512_mask_sub_epi64(r, borrow, r, _mm512_set1_epi64(0xffff_ffff))
+}
+
+/// Eight lanes of [`generic::sub`].
+#[target_feature(enable = "avx512f")]
+#[inline]
+fn sub_avx512(a: __m512i, b: __m512i) -> __m512i {
+    let r = _mm512_sub_epi64(a, b);
+    let borrow = _mm512_cmplt_epu64_mask(a, b);
+    _mm512_mask_sub_epi64(r, borrow, r, _mm512_set1_epi64(0xffff_ffff))
+}
+
+/// Eight lanes of [`generic::mont_mul`], see [`mont_mul_avx2`].
+#[target_feature(enable = "avx512f")]
+#[inline]
+fn mont_mul_avx512(a: __m512i, b: __m512i) -> __m512i {
+    let low = _mm512_set1_epi64(0xffff_ffff);
+
+    // x1 · 2^64 + x0 = a · b
+    let a_hi = _mm512_srli_epi64::<32>(a);
+    let b_hi = _mm512_srli_epi64::<32>(b);
+    let ll = _mm512_mul_epu32(a, b);
+    let lh = _mm512_mul_epu32(a, b_hi);
+    let hl = _mm512_mul_epu32(a_hi, b);
+    let hh = _mm512_mul_epu32(a_hi, b_hi);
+    let mid = _mm512_add_epi64(
+        _mm512_srli_epi64::<32>(ll),
+        _mm512_add_epi64(_mm512_and_si512(lh, low), _mm512_and_si512(hl, low)),
+    );
+    let x0 = _mm512_or_si512(_mm512_and_si512(ll, low), _mm512_slli_epi64::<32>(mid));
+    let x1 = _mm512_add_epi64(
+        _mm512_add_epi64(hh, _mm512_srli_epi64::<32>(mid)),
+        _mm512_add_epi64(_mm512_srli_epi64::<32>(lh), _mm512_srli_epi64::<32>(hl)),
+    );
+
+    // Montgomery reduction, with carries in mask registers.
+    let t = _mm512_add_epi64(x0, _mm512_slli_epi64::<32>(x0));
+    let carry = _mm512_cmplt_epu64_mask(t, x0);
+    let t = _mm512_sub_epi64(t, _mm512_srli_epi64::<32>(t));
+    let t = _mm512_mask_sub_epi64(t, carry, t, _mm512_set1_epi64(1));
+    let r = _mm512_sub_epi64(x1, t);
+    let borrow = _mm512_cmplt_epu64_mask(x1, t);
+    _mm512_mask_sub_epi64(r, borrow, r, low)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::{collection::vec, proptest};
+
+    const BACKENDS: [Backend; 3] = [Backend::Avx512, Backend::Avx2, Backend::Generic];
+
+    /// Values near the edges of the 32 bit limbs and of `p`, where the carries
+    /// in the reductions occur.
+    fn edge_values() -> Vec<u64> {
+        let centres = [0, 1 << 31, 1 << 32, 1 << 63, MODULUS - (1 << 32), MODULUS];
+        centres
+            .iter()
+            .flat_map(|&c| (0..8).map(move |d| c.wrapping_add(d).wrapping_sub(4)))
+            .filter(|&v| v < MODULUS)
+            .collect()
+    }
+
+    fn edge_pairs() -> (Vec<u64>, Vec<u64>) {
+        let edge = edge_values();
+        edge.iter()
+            .flat_map(|&a| edge.iter().map(move |&b| (a, b)))
+            .unzip()
+    }
+
+    #[test]
+    fn test_mont_mul_assign() {
+        let field = 0..MODULUS;
+        let (a, b) = edge_pairs();
+        let mut expected = a.clone();
+        generic::mont_mul_assign(&mut expected, &b);
+        for backend in BACKENDS.into_iter().filter(|b| b.is_supported()) {
+            let mut result = a.clone();
+            mont_mul_assign_with(backend, &mut result, &b);
+            assert_eq!(result, expected, "{backend:?}");
+        }
+        proptest!(|(pairs in vec((field.clone(), field), 0..100))| {
+            let (a, b): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+            let mut expected = a.clone();
+            generic::mont_mul_assign(&mut expected, &b);
+            for backend in BACKENDS.into_iter().filter(|b| b.is_supported()) {
+                let mut result = a.clone();
+                mont_mul_assign_with(backend, &mut result, &b);
+                assert_eq!(result, expected, "{backend:?}");
+            }
+        });
+    }
+
+    #[test]
+    fn test_add_sub_assign() {
+        let field = 0..MODULUS;
+        let check = |a: &[u64], b: &[u64]| {
+            let (mut expected_sum, mut expected_difference) = (a.to_vec(), a.to_vec());
+            generic::add_assign(&mut expected_sum, b);
+            generic::sub_assign(&mut expected_difference, b);
+            for backend in BACKENDS.into_iter().filter(|b| b.is_supported()) {
+                let (mut sum, mut difference) = (a.to_vec(), a.to_vec());
+                add_assign_with(backend, &mut sum, b);
+                sub_assign_with(backend, &mut difference, b);
+                assert_eq!(sum, expected_sum, "{backend:?}");
+                assert_eq!(difference, expected_difference, "{backend:?}");
+            }
+        };
+        let (a, b) = edge_pairs();
+        check(&a, &b);
+        proptest!(|(pairs in vec((field.clone(), field), 0..100))| {
+            let (a, b): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+            check(&a, &b);
+        });
+    }
+
+    #[test]
+    fn test_butterflies() {
+        let field = 0..MODULUS;
+        let (a, b) = edge_pairs();
+        let twiddles = a.iter().zip(&b).map(|(&a, &b)| a ^ b).map(|w| w % MODULUS);
+        let twiddles = twiddles.collect::<Vec<_>>();
+        let check = |low: &[u64], high: &[u64], twiddles: &[u64]| {
+            let (mut expected_low, mut expected_high) = (low.to_vec(), high.to_vec());
+            generic::butterflies(&mut expected_low, &mut expected_high, twiddles);
+            for backend in BACKENDS.into_iter().filter(|b| b.is_supported()) {
+                let (mut low, mut high) = (low.to_vec(), high.to_vec());
+                butterflies_with(backend, &mut low, &mut high, twiddles);
+                assert_eq!(low, expected_low, "{backend:?}");
+                assert_eq!(high, expected_high, "{backend:?}");
+            }
+        };
+        check(&a, &b, &twiddles);
+        proptest!(|(triples in vec((field.clone(), field.clone(), field), 0..100))| {
+            let (low, rest): (Vec<_>, Vec<_>) =
+                triples.into_iter().map(|(l, h, w)| (l, (h, w))).unzip();
+            let (high, twiddles): (Vec<_>, Vec<_>) = rest.into_iter().unzip();
+            check(&low, &high, &twiddles);
+        });
+    }
+
+    #[test]
+    fn test_butterflies_4() {
+        let field = 0..MODULUS;
+        let edge = edge_values();
+        proptest!(|(h in 0_usize..40, seed in vec(field, 8))| {
+            let value = |i: usize| {
+                let v = edge[i % edge.len()] ^ seed[i % seed.len()].wrapping_mul(i as u64 | 1);
+                v % MODULUS
+            };
+            let chunk = (0..4 * h).map(value).collect::<Vec<_>>();
+            let w1 = (0..h).map(|i| value(i + 7)).collect::<Vec<_>>();
+            let w2 = (0..2 * h).map(|i| value(3 * i + 1)).collect::<Vec<_>>();
+            let mut expected = chunk.clone();
+            generic::butterflies_4(&mut expected, &w1, &w2);
+            for backend in BACKENDS.into_iter().filter(|b| b.is_supported()) {
+                let mut result = chunk.clone();
+                butterflies_4_with(backend, &mut result, &w1, &w2);
+                assert_eq!(result, expected, "{backend:?}");
+            }
+        });
+    }
+}
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::*;
+    use criterion::{BenchmarkId, Criterion, Throughput};
+
+    pub fn group(criterion: &mut Criterion) {
+        bench_mont_mul_assign(criterion, 1 << 16);
+        bench_add_sub_assign(criterion, 1 << 16);
+        bench_butterflies(criterion, 1 << 16);
+        bench_butterflies_4(criterion, 1 << 16);
+    }
+
+    fn backends() -> impl Iterator<Item = Backend> {
+        [Backend::Generic, Backend::Avx2, Backend::Avx512]
+            .into_iter()
+            .filter(|b| b.is_supported())
+    }
+
+    fn bench_mont_mul_assign(criterion: &mut Criterion, size: usize) {
+        let mut a = (0..size as u64).collect::<Vec<_>>();
+        let b = (0..size as u64).rev().collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("field/mul_assign");
+        group.throughput(Throughput::Elements(size as u64));
+        for backend in backends() {
+            let id = BenchmarkId::new(format!("{backend:?}"), size);
+            group.bench_function(id, |bencher| {
+                bencher.iter(|| mont_mul_assign_with(backend, &mut a, &b));
+            });
+        }
+    }
+
+    fn bench_add_sub_assign(criterion: &mut Criterion, size: usize) {
+        let mut a = (0..size as u64).collect::<Vec<_>>();
+        let b = (0..size as u64).rev().collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("field/add_sub_assign");
+        group.throughput(Throughput::Elements(size as u64));
+        for backend in backends() {
+            let id = BenchmarkId::new(format!("{backend:?}"), size);
+            group.bench_function(id, |bencher| {
+                bencher.iter(|| {
+                    add_assign_with(backend, &mut a, &b);
+                    sub_assign_with(backend, &mut a, &b);
+                });
+            });
+        }
+    }
+
+    fn bench_butterflies(criterion: &mut Criterion, size: usize) {
+        let mut low = (0..size as u64).collect::<Vec<_>>();
+        let mut high = (0..size as u64).rev().collect::<Vec<_>>();
+        let twiddles = (0..size as u64).map(|i| i * i).collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("field/butterflies");
+        group.throughput(Throughput::Elements(size as u64));
+        for backend in backends() {
+            let id = BenchmarkId::new(format!("{backend:?}"), size);
+            group.bench_function(id, |bencher| {
+                bencher.iter(|| butterflies_with(backend, &mut low, &mut high, &twiddles));
+            });
+        }
+    }
+
+    /// Two radix-2 stages fused, on the same number of elements as
+    /// `bench_butterflies` does one.
+    fn bench_butterflies_4(criterion: &mut Criterion, size: usize) {
+        let mut chunk = (0..size as u64).collect::<Vec<_>>();
+        let w1 = (0..size as u64 / 4).map(|i| i * i).collect::<Vec<_>>();
+        let w2 = (0..size as u64 / 2).map(|i| i * i).collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("field/butterflies_4");
+        group.throughput(Throughput::Elements(size as u64));
+        for backend in backends() {
+            let id = BenchmarkId::new(format!("{backend:?}"), size);
+            group.bench_function(id, |bencher| {
+                bencher.iter(|| butterflies_4_with(backend, &mut chunk, &w1, &w2));
+            });
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Canonical little-endian byte encoding of field elements.
+
+use crate::Field;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Error decoding field elements from bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FromBytesError {
+    /// The input length (in bytes) is not a multiple of eight.
+    InvalidLength(usize),
+    /// The element at the given index is not less than the modulus.
+    NonCanonical(usize),
+}
+
+impl fmt::Display for FromBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(length) => {
+                write!(f, "byte length {length} is not a multiple of eight")
+            }
+            Self::NonCanonical(index) => {
+                write!(f, "element {index} is not a canonical field element")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FromBytesError {}
+
+impl Field {
+    /// The canonical value in eight little-endian bytes.
+    #[must_use]
+    pub fn to_le_bytes(self) -> [u8; 8] {
+        u64::from(self).to_le_bytes()
+    }
+
+    /// Decode an element encoded by [`Field::to_le_bytes`], or `None` if the
+    /// value is not less than the modulus.
+    #[must_use]
+    pub fn from_le_bytes(bytes: [u8; 8]) -> Option<Self> {
+        let value = u64::from_le_bytes(bytes);
+        (value < Self::MODULUS).then(|| Self::from(value))
+    }
+
+    /// Decode eight little-endian bytes, reducing the value modulo `p`.
+    #[must_use]
+    pub fn from_le_bytes_reduce(bytes: [u8; 8]) -> Self {
+        Self::from(u64::from_le_bytes(bytes) % Self::MODULUS)
+    }
+
+    /// Encode a slice of field elements as bytes.
+    ///
+    /// Each element is stored as its canonical value in eight little-endian
+    /// bytes.
+    #[must_use]
+    pub fn slice_to_bytes(values: &[Self]) -> Vec<u8> {
+        let mut result = Vec::with_capacity(values.len() * 8);
+        for value in values {
+            result.extend_from_slice(&value.to_le_bytes());
+        }
+        result
+    }
+
+    /// Decode a slice of field elements encoded by [`Field::slice_to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length is not a multiple of eight or if any
+    /// element is not less than the modulus.
+    pub fn slice_from_bytes(bytes: &[u8]) -> Result<Vec<Self>, FromBytesError> {
+        let chunks = bytes.chunks_exact(8);
+        if !chunks.remainder().is_empty() {
+            return Err(FromBytesError::InvalidLength(bytes.len()));
+        }
+        chunks
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut buffer = [0; 8];
+                buffer.copy_from_slice(chunk);
+                Self::from_le_bytes(buffer).ok_or(FromBytesError::NonCanonical(i))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let largest = Field::from(Field::MODULUS - 1);
+        for value in (0..100).map(|_| rng.gen()).chain([Field::from(0), largest]) {
+            let bytes = value.to_le_bytes();
+            assert_eq!(Field::from_le_bytes(bytes), Some(value));
+            assert_eq!(Field::from_le_bytes_reduce(bytes), value);
+        }
+        assert_eq!(largest.to_le_bytes(), (Field::MODULUS - 1).to_le_bytes());
+    }
+
+    #[test]
+    fn test_non_canonical() {
+        for value in [Field::MODULUS, u64::MAX] {
+            let bytes = value.to_le_bytes();
+            assert_eq!(Field::from_le_bytes(bytes), None);
+            assert_eq!(
+                Field::from_le_bytes_reduce(bytes),
+                Field::from(value - Field::MODULUS)
+            );
+        }
+    }
+
+    #[test]
+    fn test_slice_roundtrip() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let mut values = (0..100).map(|_| rng.gen()).collect::<Vec<Field>>();
+        values.push(Field::from(Field::MODULUS - 1));
+        let bytes = Field::slice_to_bytes(&values);
+        assert_eq!(bytes.len(), values.len() * 8);
+        assert_eq!(Field::slice_from_bytes(&bytes), Ok(values));
+    }
+
+    #[test]
+    fn test_slice_encoding() {
+        let bytes = Field::slice_to_bytes(&[Field::from(1), Field::from(Field::MODULUS - 1)]);
+        assert_eq!(bytes[..8], [1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(bytes[8..], [0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn test_slice_invalid_length() {
+        assert_eq!(
+            Field::slice_from_bytes(&[0; 12]),
+            Err(FromBytesError::InvalidLength(12))
+        );
+    }
+
+    #[test]
+    fn test_slice_non_canonical() {
+        let mut bytes = vec![0; 24];
+        bytes[8..16].copy_from_slice(&Field::MODULUS.to_le_bytes());
+        assert_eq!(
+            Field::slice_from_bytes(&bytes),
+            Err(FromBytesError::NonCanonical(1))
+        );
+    }
+}
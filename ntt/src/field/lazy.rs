@@ -0,0 +1,178 @@
+//! Lazily reduced sums of field elements and products.
+
+use super::{algo, Field};
+use core::ops;
+
+/// An unreduced sum of field elements and products of field elements.
+///
+/// Terms are accumulated as 192 bit integers and only reduced once in
+/// [`LazyField::reduce`]. A `u64` can not hold even two unreduced Goldilocks
+/// elements, so the accumulator is a `u128` with a carry word.
+///
+/// Each term is less than `p²` and the reduction takes values below `2^159`,
+/// so at most [`LazyField::MAX_TERMS`] terms can be accumulated. Debug builds
+/// track the number of terms and panic when this is exceeded. In release
+/// builds the count is not stored.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LazyField {
+    low:   u128,
+    high:  u64,
+    #[cfg(debug_assertions)]
+    terms: u64,
+}
+
+impl LazyField {
+    /// Maximum number of terms, the largest `n` with `n · (p - 1)² < 2^159`
+    /// rounded down to a power of two.
+    pub const MAX_TERMS: u64 = 1 << 31;
+
+    /// The product `a · b` as a single term.
+    #[inline(always)]
+    #[must_use]
+    pub fn product(a: Field, b: Field) -> Self {
+        // Montgomery form: a·R · b·R is the product times R².
+        Self::term(u128::from(a.0) * u128::from(b.0))
+    }
+
+    /// Add the product `a · b`.
+    #[inline(always)]
+    pub fn add_product(&mut self, a: Field, b: Field) {
+        *self += Self::product(a, b);
+    }
+
+    /// The dot product `Σ a_i · b_i` of the common prefix of `a` and `b`,
+    /// with one reduction per [`LazyField::MAX_TERMS`] terms.
+    #[must_use]
+    pub fn dot(a: &[Field], b: &[Field]) -> Field {
+        let chunk = usize::try_from(Self::MAX_TERMS).unwrap_or(usize::MAX);
+        a.chunks(chunk)
+            .zip(b.chunks(chunk))
+            .map(|(a, b)| {
+                let mut sum = Self::default();
+                for (&a, &b) in a.iter().zip(b) {
+                    sum.add_product(a, b);
+                }
+                sum.reduce()
+            })
+            .sum()
+    }
+
+    /// Reduce to a field element.
+    #[inline(always)]
+    #[must_use]
+    pub fn reduce(self) -> Field {
+        debug_assert!(self.high < 1 << 31);
+        #[allow(clippy::cast_possible_truncation)]
+        let reduced = algo::generic::reduce_159(
+            self.low as u64,
+            (self.low >> 64) as u32,
+            ((self.low >> 96) as u64) | self.high << 32,
+        );
+        // Remove the extra factor R.
+        Field(algo::mont_mul(reduced, 1))
+    }
+
+    #[inline(always)]
+    const fn term(value: u128) -> Self {
+        Self {
+            low: value,
+            high: 0,
+            #[cfg(debug_assertions)]
+            terms: 1,
+        }
+    }
+}
+
+impl From<Field> for LazyField {
+    #[inline(always)]
+    fn from(value: Field) -> Self {
+        // Scale by R to match the products.
+        Self::term(u128::from(value.0) * u128::from(algo::MONT_R1))
+    }
+}
+
+impl ops::Add for LazyField {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl ops::AddAssign for LazyField {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        #[cfg(debug_assertions)]
+        {
+            self.terms += rhs.terms;
+            assert!(
+                self.terms <= Self::MAX_TERMS,
+                "LazyField accumulation bound exceeded."
+            );
+        }
+        let (low, carry) = self.low.overflowing_add(rhs.low);
+        self.low = low;
+        self.high += rhs.high + u64::from(carry);
+    }
+}
+
+impl ops::AddAssign<Field> for LazyField {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Field) {
+        *self += Self::from(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_sum_of_products() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let a = (0..1000).map(|_| rng.gen()).collect::<Vec<Field>>();
+        let b = (0..1000).map(|_| rng.gen()).collect::<Vec<Field>>();
+        let c: Field = rng.gen();
+        let mut lazy = LazyField::from(c);
+        for (&a, &b) in a.iter().zip(&b) {
+            lazy.add_product(a, b);
+        }
+        let expected = c + a.iter().zip(&b).map(|(&a, &b)| a * b).sum::<Field>();
+        assert_eq!(lazy.reduce(), expected);
+    }
+
+    #[test]
+    fn test_dot() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let a = (0..100).map(|_| rng.gen()).collect::<Vec<Field>>();
+        let b = (0..90).map(|_| rng.gen()).collect::<Vec<Field>>();
+        let expected = a.iter().zip(&b).map(|(&a, &b)| a * b).sum::<Field>();
+        assert_eq!(LazyField::dot(&a, &b), expected);
+        assert_eq!(LazyField::dot(&[], &b), Field::from(0));
+    }
+
+    #[test]
+    fn test_max_terms() {
+        // The largest term, doubled up to the bound.
+        let max = Field::from(Field::MODULUS - 1);
+        let mut lazy = LazyField::product(max, max);
+        for _ in 0..31 {
+            lazy += lazy;
+        }
+        let expected = max * max * Field::from(LazyField::MAX_TERMS);
+        assert_eq!(lazy.reduce(), expected);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "bound exceeded")]
+    fn test_bound_exceeded() {
+        let mut lazy = LazyField::from(Field::from(1));
+        for _ in 0..32 {
+            lazy += lazy;
+        }
+    }
+}
@@ -1,12 +1,18 @@
 pub mod algo;
+mod bytes;
+mod lazy;
+mod parse;
 mod rand;
+mod serde;
 
-use core::{iter, ops};
-use std::{fmt, ops::Neg};
+pub use self::{bytes::FromBytesError, lazy::LazyField, parse::ParseFieldError};
+
+use core::{fmt, iter, ops, ops::Neg};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
 
 /// An element in the Goldilocks field.
 #[derive(Clone, Copy, PartialEq, Eq, Default, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(bytemuck::TransparentWrapper, bytemuck::Zeroable, bytemuck::Pod)]
 #[repr(transparent)]
 pub struct Field(u64);
@@ -26,19 +32,103 @@ impl Field {
         Self(value)
     }
 
+    /// The element `value`, or `None` if `value` is not less than the modulus.
+    #[inline(always)]
+    #[must_use]
+    pub fn from_canonical(value: u64) -> Option<Self> {
+        (value < Self::MODULUS).then(|| Self::reduce_u64(value))
+    }
+
+    /// The representative in `[0, p)`, the same as `u64::from`.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_canonical_u64(self) -> u64 {
+        self.into()
+    }
+
+    /// Reduce an arbitrary `u128` modulo the prime, using `2^64 = 2^32 - 1`
+    /// and `2^96 = -1`. The same as `Field::from`.
+    #[inline(always)]
+    #[must_use]
+    pub fn reduce_u128(value: u128) -> Self {
+        Self(algo::mont_mul(algo::reduce_128(value), algo::MONT_R2))
+    }
+
+    /// Reduce an arbitrary `u64` modulo the prime, which takes at most one
+    /// subtraction. The same as `Field::from`.
+    #[inline(always)]
+    #[must_use]
+    pub fn reduce_u64(value: u64) -> Self {
+        Self(algo::mont_mul(algo::reduce_64(value), algo::MONT_R2))
+    }
+
+    /// Whether the internal representation is reduced, i.e. less than the
+    /// modulus. All values constructed through the public API are canonical.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_canonical(self) -> bool {
+        self.0 < Self::MODULUS
+    }
+
     /// Inverse of the field element, or zero.
+    ///
+    /// Zero has no inverse, this returns zero for it and never panics. See
+    /// [`Self::inv_or_zero`] to make that explicit at the call site.
     #[inline(always)]
     #[must_use]
     pub fn inv(self) -> Self {
         Self(algo::mont_mul(algo::inv(self.0), algo::MONT_R3))
     }
 
+    /// Inverse of the field element, or zero for zero. The same as
+    /// [`Self::inv`].
+    #[inline(always)]
+    #[must_use]
+    pub fn inv_or_zero(self) -> Self {
+        self.inv()
+    }
+
+    /// Inverse of the field element, or zero, in constant time.
+    ///
+    /// Computes `self^(p - 2)` with an addition chain of 72 multiplications,
+    /// see [`algo::mont_inv_addchain`]. The chain uses `p - 2 = 2(2^31 -
+    /// 1)(2^32 + 1) + 1` and `2^31 - 1 = 2(2^15 - 1)(2^15 + 1) + 1`, with an
+    /// optimal chain for `2^15 - 1`. Square and multiply would take 127.
+    #[inline]
+    #[must_use]
+    pub fn inv_ct(self) -> Self {
+        Self(algo::mont_inv_addchain(self.0))
+    }
+
+    /// Raise to the power `exp` by square and multiply. Not constant time.
     #[inline(always)]
     #[must_use]
     pub fn pow(self, exp: u64) -> Self {
         Self(algo::mont_pow(self.0, exp))
     }
 
+    /// Square `n` times, i.e. raise to the power `2^n`. Unlike
+    /// `pow(1 << n)` this works for `n >= 64` and skips the multiplications
+    /// of square and multiply.
+    #[inline]
+    #[must_use]
+    pub fn square_n(self, n: u32) -> Self {
+        let mut result = self.0;
+        for _ in 0..n {
+            result = algo::mont_mul(result, result);
+        }
+        Self(result)
+    }
+
+    /// Raise to the power of a multi-limb exponent, given as little-endian
+    /// `u64` limbs. Not constant time.
+    #[must_use]
+    pub fn pow_wide(self, exp: &[u64]) -> Self {
+        exp.iter().rev().fold(Self::new(1), |result, &limb| {
+            result.square_n(64) * self.pow(limb)
+        })
+    }
+
     #[inline(always)]
     #[must_use]
     pub fn root(order: u64) -> Option<Self> {
@@ -48,6 +138,160 @@ impl Field {
         let exponent = algo::ORDER / order;
         Some(Self(algo::mont_pow(algo::GENERATOR_R, exponent)))
     }
+
+    /// Orders of all subgroups of the multiplicative group, i.e. the divisors
+    /// of `p - 1 = 2^32 · 3 · 5 · 17 · 257 · 65537` in increasing order.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn subgroup_orders() -> Vec<u64> {
+        crate::divisors::divisors().to_vec()
+    }
+
+    /// A generator of the subgroup of order `order`, or `None` if `order` does
+    /// not divide `p - 1`. The same as [`Field::root`].
+    #[must_use]
+    pub fn subgroup_generator(order: u64) -> Option<Self> {
+        Self::root(order)
+    }
+
+    /// Raise each of `bases` to the power `exp`, storing the results in `out`.
+    ///
+    /// Square and multiply on whole chunks of bases at once, using the slice
+    /// multiply [`algo::mul_assign_slice`] and so AVX-512 or AVX2 when
+    /// available. Chunks are small enough to stay in cache.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bases` and `out` have different lengths.
+    pub fn pow_slice(bases: &[Self], exp: u64, out: &mut [Self]) {
+        const CHUNK: usize = 256;
+        assert_eq!(bases.len(), out.len());
+        let mut squares = [Self::MIN; CHUNK];
+        let mut copy = [Self::MIN; CHUNK];
+        for (bases, out) in bases.chunks(CHUNK).zip(out.chunks_mut(CHUNK)) {
+            let squares = &mut squares[..bases.len()];
+            let copy = &mut copy[..bases.len()];
+            squares.copy_from_slice(bases);
+            out.fill(Self::from(1));
+            let mut e = exp;
+            while e > 0 {
+                if e & 1 == 1 {
+                    algo::mul_assign_slice(out, squares);
+                }
+                e >>= 1;
+                if e > 0 {
+                    copy.copy_from_slice(squares);
+                    algo::mul_assign_slice(squares, copy);
+                }
+            }
+        }
+    }
+
+    /// Primitive root of unity of order `2^bits`, or `None` if `bits > 32`.
+    ///
+    /// Reads from a tower of roots computed once, so this is cheap to call
+    /// repeatedly. Without `std` the tower is recomputed on each call.
+    #[must_use]
+    pub fn two_adic_root(bits: u32) -> Option<Self> {
+        #[cfg(feature = "std")]
+        let tower = {
+            static TOWER: OnceLock<[Field; 33]> = OnceLock::new();
+            TOWER.get_or_init(Self::two_adic_tower)
+        };
+        #[cfg(not(feature = "std"))]
+        let tower = &Self::two_adic_tower();
+        tower.get(bits as usize).copied()
+    }
+
+    /// Primitive root of unity of order `order`, or `None` unless `order` is a
+    /// power of two of at most `2^32`.
+    ///
+    /// A table lookup in the tower of [`Field::two_adic_root`]. Agrees with
+    /// [`Field::root`], which also handles the odd orders.
+    #[must_use]
+    pub fn root_of_unity(order: u64) -> Option<Self> {
+        if order.is_power_of_two() {
+            Self::two_adic_root(order.trailing_zeros())
+        } else {
+            None
+        }
+    }
+
+    fn two_adic_tower() -> [Self; 33] {
+        let mut tower = [Self::new(1); 33];
+        tower[32] = Self(algo::mont_pow(algo::GENERATOR_R, algo::ORDER >> 32));
+        for k in (0..32).rev() {
+            tower[k] = tower[k + 1].square_n(1);
+        }
+        tower
+    }
+
+    /// The Legendre symbol `self^((p - 1) / 2)`: `0` for zero, `1` for a
+    /// non-zero square and `-1` otherwise.
+    #[must_use]
+    pub fn legendre(self) -> i8 {
+        let symbol = self.pow((Self::MODULUS - 1) / 2);
+        if symbol == Self::new(0) {
+            0
+        } else if symbol == Self::new(1) {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Whether `self` is a square, including zero.
+    #[must_use]
+    pub fn is_square(self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Square root by Tonelli–Shanks, or `None` if `self` is not a square.
+    ///
+    /// Of the two roots `±r` this returns the one with the smaller canonical
+    /// value, i.e. at most `(p - 1) / 2`.
+    #[must_use]
+    pub fn sqrt(self) -> Option<Self> {
+        // p - 1 = 2^32 · q with q = 2^32 - 1.
+        const Q: u64 = (1 << 32) - 1;
+        let one = Self::new(1);
+        if self == Self::new(0) {
+            return Some(self);
+        }
+        let mut bits = 32;
+        let mut c = Self::two_adic_root(32)?;
+        let mut t = self.pow(Q);
+        let mut r = self.pow(Q.div_ceil(2));
+        while t != one {
+            let mut order = 0;
+            let mut square = t;
+            while square != one {
+                square = square * square;
+                order += 1;
+            }
+            if order == bits {
+                return None;
+            }
+            let factor = c.square_n(bits - order - 1);
+            bits = order;
+            c = factor * factor;
+            t *= c;
+            r *= factor;
+        }
+        Some(r.min(-r))
+    }
+
+    /// The geometric sum `1 + x + x^2 + ... + x^(n-1)`, computed with the
+    /// closed form `(x^n - 1) / (x - 1)`. For `x = 1` the sum is `n`.
+    #[must_use]
+    pub fn geometric_sum(x: Self, n: u64) -> Self {
+        let one = Self::new(1);
+        if x == one {
+            Self::from(n)
+        } else {
+            (x.pow(n) - one) / (x - one)
+        }
+    }
 }
 
 impl fmt::Debug for Field {
@@ -65,8 +309,15 @@ impl fmt::Display for Field {
     }
 }
 
+impl fmt::LowerHex for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = u64::from(self);
+        <u64 as fmt::LowerHex>::fmt(&n, f)
+    }
+}
+
 impl PartialOrd for Field {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         let a = u64::from(self);
         let b = u64::from(other);
         a.partial_cmp(&b)
@@ -74,7 +325,7 @@ impl PartialOrd for Field {
 }
 
 impl Ord for Field {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         let a = u64::from(self);
         let b = u64::from(other);
         a.cmp(&b)
@@ -84,12 +335,26 @@ impl Ord for Field {
 impl From<i32> for Field {
     #[inline(always)]
     fn from(value: i32) -> Self {
-        #[allow(clippy::cast_sign_loss)]
+        let magnitude = Self(algo::mont_mul(
+            u64::from(value.unsigned_abs()),
+            algo::MONT_R2,
+        ));
         if value >= 0 {
-            debug_assert!((value as u64) < Self::MODULUS);
-            Self(algo::mont_mul(value as u64, algo::MONT_R2))
+            magnitude
         } else {
-            Self(algo::mont_mul(-value as u64, algo::MONT_R2)).neg()
+            magnitude.neg()
+        }
+    }
+}
+
+impl From<i64> for Field {
+    #[inline(always)]
+    fn from(value: i64) -> Self {
+        let magnitude = Self::from(value.unsigned_abs());
+        if value >= 0 {
+            magnitude
+        } else {
+            magnitude.neg()
         }
     }
 }
@@ -97,14 +362,14 @@ impl From<i32> for Field {
 impl From<u64> for Field {
     #[inline(always)]
     fn from(value: u64) -> Self {
-        Self(algo::mont_mul(algo::reduce_64(value), algo::MONT_R2))
+        Self::reduce_u64(value)
     }
 }
 
 impl From<u128> for Field {
     #[inline(always)]
     fn from(value: u128) -> Self {
-        Self(algo::mont_mul(algo::reduce_128(value), algo::MONT_R2))
+        Self::reduce_u128(value)
     }
 }
 
@@ -144,6 +409,12 @@ impl iter::Sum for Field {
     }
 }
 
+impl<'a> iter::Sum<&'a Self> for Field {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
 impl ops::Neg for Field {
     type Output = Self;
 
@@ -187,7 +458,13 @@ impl ops::MulAssign for Field {
 
 impl iter::Product for Field {
     fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
-        Self(iter.fold(1, |a, n| algo::mont_mul(a, n.0)))
+        Self(iter.fold(algo::MONT_R1, |a, n| algo::mont_mul(a, n.0)))
+    }
+}
+
+impl<'a> iter::Product<&'a Self> for Field {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
     }
 }
 
@@ -339,6 +616,94 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_from_canonical() {
+        assert_eq!(Field::from_canonical(0), Some(Field::new(0)));
+        assert_eq!(
+            Field::from_canonical(Field::MODULUS - 1),
+            Some(Field::from(-1))
+        );
+        assert_eq!(Field::from_canonical(Field::MODULUS), None);
+        assert_eq!(Field::from_canonical(u64::MAX), None);
+        proptest!(|(value: u64)| {
+            if let Some(field) = Field::from_canonical(value) {
+                assert_eq!(field.as_canonical_u64(), value);
+            } else {
+                assert!(value >= Field::MODULUS);
+            }
+        });
+        proptest!(|(a: Field)| {
+            assert_eq!(Field::from_canonical(a.as_canonical_u64()), Some(a));
+        });
+    }
+
+    #[test]
+    fn test_reduce() {
+        let p = u128::from(Field::MODULUS);
+        let reference = |value: u128| Field::new((value % p) as u64);
+        for value in [
+            0,
+            1,
+            p - 1,
+            p,
+            p + 1,
+            2 * p - 1,
+            2 * p,
+            u128::from(u64::MAX),
+            1 << 64,
+            1 << 96,
+            (1 << 96) - 1,
+            p * p - 1,
+            p * p,
+            u128::MAX - 1,
+            u128::MAX,
+        ] {
+            assert_eq!(Field::reduce_u128(value), reference(value), "{value}");
+        }
+        for value in [
+            0,
+            1,
+            Field::MODULUS - 1,
+            Field::MODULUS,
+            Field::MODULUS + 1,
+            u64::MAX,
+        ] {
+            assert_eq!(Field::reduce_u64(value), reference(value.into()), "{value}");
+        }
+        proptest!(|(value: u128)| {
+            assert_eq!(Field::reduce_u128(value), reference(value));
+            assert_eq!(Field::reduce_u64(value as u64), reference(u128::from(value as u64)));
+        });
+    }
+
+    #[test]
+    fn test_from_signed() {
+        assert_eq!(Field::from(-1) + Field::from(1), Field::from(0));
+        assert_eq!(Field::from(-1_i64), Field::from(Field::MODULUS - 1));
+        assert_eq!(Field::from(i32::MIN), -Field::from(1_u64 << 31));
+        assert_eq!(Field::from(i64::MIN), -Field::from(1_u64 << 63));
+        assert_eq!(Field::from(i64::MAX), Field::from(i64::MAX as u64));
+        assert_eq!(Field::from(i64::MIN + 1), -Field::from(i64::MAX as u64));
+        assert_eq!(
+            Field::from(-0x1234_5678_9abc_def0_i64),
+            Field::from(Field::MODULUS - 0x1234_5678_9abc_def0)
+        );
+    }
+
+    #[test]
+    fn test_sum_product() {
+        proptest!(|(values: Vec<Field>)| {
+            let sum = values.iter().fold(Field::new(0), |a, &b| a + b);
+            let product = values.iter().fold(Field::new(1), |a, &b| a * b);
+            assert_eq!(values.iter().copied().sum::<Field>(), sum);
+            assert_eq!(values.iter().sum::<Field>(), sum);
+            assert_eq!(values.iter().copied().product::<Field>(), product);
+            assert_eq!(values.iter().product::<Field>(), product);
+        });
+        assert_eq!(iter::empty::<Field>().sum::<Field>(), Field::new(0));
+        assert_eq!(iter::empty::<Field>().product::<Field>(), Field::new(1));
+    }
+
     #[test]
     fn test_root() {
         let omega_5 = Field::root(5).unwrap();
@@ -347,15 +712,231 @@ mod tests {
         assert_eq!(omega_4.pow(4), Field::new(1));
         assert_eq!(omega_4.pow(2), -Field::new(1));
     }
+
+    #[test]
+    fn test_pow() {
+        let one = Field::new(1);
+        let generator = Field::new(algo::GENERATOR);
+        assert_eq!(generator.pow(Field::MODULUS - 1), one);
+        assert_eq!(generator.pow((Field::MODULUS - 1) / 2), -one);
+        assert_eq!(generator.pow(0), one);
+        assert_eq!(Field::new(0).pow(0), one);
+        assert_eq!(Field::new(3).pow(5), Field::new(243));
+    }
+
+    #[test]
+    fn test_pow_wide() {
+        let one = Field::new(1);
+        let generator = Field::new(algo::GENERATOR);
+        assert_eq!(generator.pow_wide(&[]), one);
+        assert_eq!(generator.pow_wide(&[Field::MODULUS - 1, 0]), one);
+        // 2^64 = 2^32 (mod p - 1), so g^(2^64) = g^(2^32).
+        assert_eq!(generator.pow_wide(&[0, 1]), generator.pow(1 << 32));
+        proptest!(|(a: Field, lo: u64, hi: u64)| {
+            assert_eq!(a.pow_wide(&[lo]), a.pow(lo));
+            let high = a.pow(hi).pow(1 << 32).pow(1 << 32);
+            assert_eq!(a.pow_wide(&[lo, hi, 0]), high * a.pow(lo));
+        });
+    }
+
+    #[test]
+    fn test_inv() {
+        assert_eq!(Field::new(0).inv(), Field::new(0));
+        assert_eq!(Field::new(0).inv_or_zero(), Field::new(0));
+        assert_eq!(Field::new(1).inv(), Field::new(1));
+        proptest!(|(a: Field)| {
+            assert_eq!(a.inv_or_zero(), a.inv());
+            assert_eq!(a.inv().inv(), a);
+            if a != Field::new(0) {
+                assert_eq!(a * a.inv(), Field::new(1));
+            }
+        });
+    }
+
+    #[test]
+    fn test_inv_ct() {
+        assert_eq!(Field::new(0).inv_ct(), Field::new(0));
+        proptest!(|(a: Field)| {
+            assert_eq!(a.inv_ct(), a.inv());
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "counters")]
+    fn test_inv_ct_count() {
+        let a = Field::new(12345);
+        algo::counters::reset();
+        let _ = a.inv_ct();
+        let chain = algo::counters::mul_count();
+        algo::counters::reset();
+        let _ = a.pow(Field::MODULUS - 2);
+        let binary = algo::counters::mul_count();
+        assert_eq!(chain, 72);
+        assert!(chain < binary);
+    }
+
+    #[test]
+    fn test_pow_slice() {
+        proptest!(|(bases in proptest::collection::vec(Field::arbitrary(), 0..600), exp: u64)| {
+            let mut out = vec![Field::new(0); bases.len()];
+            Field::pow_slice(&bases, exp, &mut out);
+            for (base, out) in bases.iter().zip(out) {
+                assert_eq!(out, base.pow(exp));
+            }
+        });
+    }
+
+    #[test]
+    fn test_square_n() {
+        let generator = Field::new(algo::GENERATOR);
+        for k in 0..64 {
+            assert_eq!(generator.square_n(k), generator.pow(1 << k));
+        }
+        // g^(2^32) generates the subgroup of odd order `2^32 - 1`.
+        assert_eq!(generator.square_n(32), Field::root((1 << 32) - 1).unwrap());
+        assert_eq!(generator.square_n(64), generator.pow(1 << 32).pow(1 << 32));
+        proptest!(|(a: Field, k in 0_u32..64)| {
+            assert_eq!(a.square_n(k), a.pow(1 << k));
+        });
+    }
+
+    #[test]
+    fn test_two_adic_root() {
+        let one = Field::new(1);
+        for k in 0..=32 {
+            let root = Field::two_adic_root(k).unwrap();
+            assert_eq!(root.pow(1 << k), one);
+            if k > 0 {
+                assert_ne!(root.pow(1 << (k - 1)), one);
+                assert_eq!(root * root, Field::two_adic_root(k - 1).unwrap());
+            }
+        }
+        assert_eq!(Field::two_adic_root(33), None);
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        let one = Field::new(1);
+        for k in 0..=32 {
+            let n = 1_u64 << k;
+            let root = Field::root_of_unity(n).unwrap();
+            assert_eq!(root.pow(n), one);
+            if n > 1 {
+                assert_ne!(root.pow(n / 2), one);
+            }
+            assert_eq!(Some(root), Field::root(n));
+        }
+        for order in [0, 3, 6, 1 << 33, Field::MODULUS - 1] {
+            assert_eq!(Field::root_of_unity(order), None);
+        }
+    }
+
+    #[test]
+    fn test_legendre() {
+        // p = 1 (mod 8) and p = 1 (mod 3), 1 (mod 5), 6 (mod 7), so by
+        // reciprocity 2, 3 and 5 are squares and 7 is not.
+        let expected = [0, 1, 1, 1, 1, 1, 1, -1, 1, 1];
+        for (n, expected) in expected.into_iter().enumerate() {
+            assert_eq!(Field::from(n as u64).legendre(), expected);
+        }
+        assert_eq!((-Field::new(1)).legendre(), 1);
+        assert_eq!(Field::new(algo::GENERATOR).legendre(), -1);
+        for n in 0_u64..1000 {
+            let x = Field::from(n);
+            assert_eq!(x.is_square(), x.sqrt().is_some());
+            assert_eq!((x * x).legendre(), i8::from(n != 0));
+        }
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(Field::new(0).sqrt(), Some(Field::new(0)));
+        assert_eq!(Field::new(1).sqrt(), Some(Field::new(1)));
+        assert_eq!(Field::new(4).sqrt(), Some(Field::new(2)));
+        proptest!(|(x: Field)| {
+            let square = x * x;
+            let root = square.sqrt().unwrap();
+            assert_eq!(root * root, square);
+            assert!(root == x || root == -x);
+            assert!(u64::from(root) <= (Field::MODULUS - 1) / 2);
+        });
+    }
+
+    #[test]
+    fn test_sqrt_non_residue() {
+        let generator = Field::new(algo::GENERATOR);
+        assert_eq!(generator.sqrt(), None);
+        proptest!(|(x: Field)| {
+            prop_assume!(x != Field::new(0));
+            assert_eq!((generator * x * x).sqrt(), None);
+        });
+    }
+
+    #[test]
+    fn test_subgroup_generator() {
+        let one = Field::new(1);
+        let orders = Field::subgroup_orders();
+        assert_eq!(orders.len(), 33 * 32);
+        assert_eq!(orders.last(), Some(&(Field::MODULUS - 1)));
+        for order in orders {
+            let generator = Field::subgroup_generator(order).unwrap();
+            assert_eq!(generator.pow(order), one);
+            for prime in [2, 3, 5, 17, 257, 65537] {
+                if order % prime == 0 {
+                    assert_ne!(generator.pow(order / prime), one);
+                }
+            }
+        }
+        assert_eq!(Field::subgroup_generator(7), None);
+        assert_eq!(Field::subgroup_generator(0), None);
+    }
+
+    #[test]
+    fn test_geometric_sum() {
+        proptest!(|(x: Field, n in 0_u64..100)| {
+            let expected = (0..n).map(|i| x.pow(i)).sum::<Field>();
+            assert_eq!(Field::geometric_sum(x, n), expected);
+        });
+    }
+
+    #[test]
+    fn test_geometric_sum_one() {
+        assert_eq!(Field::geometric_sum(Field::new(1), 0), Field::new(0));
+        assert_eq!(Field::geometric_sum(Field::new(1), 17), Field::new(17));
+        assert_eq!(
+            Field::geometric_sum(Field::new(1), Field::MODULUS + 2),
+            Field::new(2)
+        );
+    }
 }
 
 #[cfg(feature = "bench")]
 #[doc(hidden)]
 pub mod bench {
     use super::*;
-    use criterion::Criterion;
+    use crate::bench::rand_vec;
+    use criterion::{BenchmarkId, Criterion, Throughput};
 
     pub fn group(criterion: &mut Criterion) {
         algo::bench::group(criterion);
+        bench_pow_slice(criterion, 1 << 16);
+    }
+
+    fn bench_pow_slice(criterion: &mut Criterion, size: usize) {
+        let bases: Vec<Field> = rand_vec(size);
+        let exp = Field::MODULUS - 2;
+        let mut out = vec![Field::new(0); size];
+        let mut group = criterion.benchmark_group("field/pow_slice");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("pow", size), |bencher| {
+            bencher.iter(|| {
+                for (out, base) in out.iter_mut().zip(&bases) {
+                    *out = base.pow(exp);
+                }
+            });
+        });
+        group.bench_function(BenchmarkId::new("pow_slice", size), |bencher| {
+            bencher.iter(|| Field::pow_slice(&bases, exp, &mut out));
+        });
     }
 }
@@ -0,0 +1,89 @@
+//! Parsing field elements from decimal and hexadecimal strings.
+
+use crate::Field;
+use core::{fmt, num::ParseIntError, str::FromStr};
+
+/// Error parsing a field element from a string.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseFieldError {
+    /// The string is not a valid integer.
+    Invalid(ParseIntError),
+    /// The integer is not less than the modulus.
+    OutOfRange,
+}
+
+impl fmt::Display for ParseFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(error) => write!(f, "invalid field element: {error}"),
+            Self::OutOfRange => write!(f, "field element is not less than the modulus"),
+        }
+    }
+}
+
+impl core::error::Error for ParseFieldError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Invalid(error) => Some(error),
+            Self::OutOfRange => None,
+        }
+    }
+}
+
+impl FromStr for Field {
+    type Err = ParseFieldError;
+
+    /// Parse a canonical value in decimal, or in hexadecimal with a `0x`
+    /// prefix. Values not less than the modulus are rejected rather than
+    /// reduced.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s
+            .strip_prefix("0x")
+            .map_or_else(|| s.parse(), |hex| u64::from_str_radix(hex, 16))
+            .map_err(ParseFieldError::Invalid)?;
+        if value < Self::MODULUS {
+            Ok(Self::from(value))
+        } else {
+            Err(ParseFieldError::OutOfRange)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use proptest::proptest;
+
+    #[test]
+    fn test_parse() {
+        let largest = Field::from(Field::MODULUS - 1);
+        assert_eq!("0xffffffff00000000".parse(), Ok(largest));
+        assert_eq!("0xffffffff00000000".parse(), Ok(-Field::from(1)));
+        assert_eq!("18446744069414584320".parse(), Ok(largest));
+        assert_eq!("0".parse(), Ok(Field::from(0)));
+        assert_eq!("0x2a".parse(), Ok(Field::from(42)));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for s in ["", "0x", "abc", "-1", "0xg", "1.0", " 1"] {
+            assert!(matches!(
+                s.parse::<Field>(),
+                Err(ParseFieldError::Invalid(_))
+            ));
+        }
+        for s in ["0xffffffff00000001", "18446744073709551615"] {
+            assert_eq!(s.parse::<Field>(), Err(ParseFieldError::OutOfRange));
+        }
+    }
+
+    #[test]
+    fn test_format_roundtrip() {
+        proptest!(|(value: Field)| {
+            assert_eq!(format!("{value}").parse(), Ok(value));
+            assert_eq!(format!("{value:#x}").parse(), Ok(value));
+            assert_eq!(format!("{value:x}"), format!("{:x}", u64::from(value)));
+        });
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "serde")]
+//! Serialize field elements as their canonical value, independent of the
+//! internal Montgomery representation.
+
+use crate::Field;
+use core::fmt;
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+impl Serialize for Field {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(u64::from(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_u64(FieldVisitor)
+    }
+}
+
+struct FieldVisitor;
+
+impl Visitor<'_> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an integer less than {}", Field::MODULUS)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Field, E> {
+        if value < Field::MODULUS {
+            Ok(Field::from(value))
+        } else {
+            Err(E::invalid_value(de::Unexpected::Unsigned(value), &self))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::proptest;
+
+    #[test]
+    fn test_canonical() {
+        // The internal representation of one is `2^32 - 1`.
+        let one = Field::from(1);
+        assert_ne!(bytemuck::cast::<_, u64>(one), 1);
+        assert_eq!(serde_json::to_string(&one).unwrap(), "1");
+        assert_eq!(serde_json::from_str::<Field>("1").unwrap(), one);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        proptest!(|(value: Field)| {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(json, u64::from(value).to_string());
+            assert_eq!(serde_json::from_str::<Field>(&json).unwrap(), value);
+        });
+    }
+
+    #[test]
+    fn test_largest() {
+        let value = Field::from(Field::MODULUS - 1);
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, (Field::MODULUS - 1).to_string());
+        assert_eq!(serde_json::from_str::<Field>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn test_non_canonical() {
+        let json = Field::MODULUS.to_string();
+        assert!(serde_json::from_str::<Field>(&json).is_err());
+        assert!(serde_json::from_str::<Field>("-1").is_err());
+    }
+
+    #[test]
+    fn test_bincode() {
+        proptest!(|(value: Field)| {
+            let bytes = bincode::serialize(&value).unwrap();
+            assert_eq!(bytes, u64::from(value).to_le_bytes());
+            assert_eq!(bincode::deserialize::<Field>(&bytes).unwrap(), value);
+        });
+        let largest = (Field::MODULUS - 1).to_le_bytes();
+        assert_eq!(
+            bincode::deserialize::<Field>(&largest).unwrap(),
+            Field::from(Field::MODULUS - 1)
+        );
+        assert!(bincode::deserialize::<Field>(&Field::MODULUS.to_le_bytes()).is_err());
+        assert!(bincode::deserialize::<Field>(&u64::MAX.to_le_bytes()).is_err());
+    }
+}
@@ -12,13 +12,27 @@
 )]
 // See <https://stackoverflow.com/questions/61417452/how-to-get-a-feature-requirement-tag-in-the-documentation-generated-by-cargo-do>
 #![cfg_attr(has_doc_cfg, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
 // #![feature(slice_swap_unchecked)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
 pub mod convolve;
+#[cfg(feature = "std")]
 pub mod divisors;
+pub mod ext;
 pub mod field;
+#[cfg(feature = "std")]
 pub mod ntt;
+#[cfg(feature = "std")]
 pub mod permute;
+#[cfg(feature = "std")]
+pub mod poly;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+#[cfg(feature = "hash")]
+pub mod transcript;
 pub mod utils;
 
 pub use field::Field;
@@ -41,6 +55,7 @@ pub mod bench {
     pub fn group(criterion: &mut Criterion) {
         field::bench::group(criterion);
         ntt::bench::group(criterion);
+        permute::bench::group(criterion);
         // ntt2::bench::group(criterion);
     }
 
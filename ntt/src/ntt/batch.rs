@@ -0,0 +1,173 @@
+//! Parallel batches of power-of-two NTTs.
+//!
+//! Memory model: the twiddle table for a size is computed once, cached and
+//! shared as an immutable `Arc<[Field]>`. Workers only read it, so there are
+//! no writes to shared cache lines. The tables stay until
+//! [`super::clear_cache`]. Each worker thread has its own scratch buffer for
+//! the bit-reversal permutation, which is reused across vectors and calls.
+//! Threads keep buffers of up to `MAX_SCRATCH` elements, larger ones are
+//! freed after use. The vectors themselves are disjoint chunks of the input.
+
+use super::cooley_tukey::ntt_u64;
+use crate::{
+    permute::{permutation, permute_wo_oop},
+    Field,
+};
+use bytemuck::TransparentWrapper;
+use rayon::prelude::*;
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+/// Largest scratch buffer, in elements, kept by a thread between calls.
+const MAX_SCRATCH: usize = 1 << 16;
+
+static TWIDDLES: Mutex<BTreeMap<usize, Arc<[Field]>>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    static SCRATCH: Cell<Vec<Field>> = const { Cell::new(Vec::new()) };
+}
+
+/// The table `ω^i` for `i < size / 2`, computed once per size.
+///
+/// # Panics
+///
+/// Panics if `size` is not a power of two dividing the multiplicative group
+/// order.
+#[must_use]
+pub fn twiddles(size: usize) -> Arc<[Field]> {
+    assert!(size.is_power_of_two());
+    let mut lock = TWIDDLES.lock().unwrap();
+    lock.entry(size)
+        .or_insert_with(|| {
+            let root = Field::root(size as u64)
+                .expect("Vector length does not divide multiplicative group order.");
+            let mut omega = Field::from(1);
+            (0..size / 2)
+                .map(|_| {
+                    let result = omega;
+                    omega *= root;
+                    result
+                })
+                .collect()
+        })
+        .clone()
+}
+
+/// Free the tables of [`twiddles`]. Also done by [`super::clear_cache`].
+///
+/// # Panics
+///
+/// Panics if the table lock is poisoned.
+pub fn clear_twiddles() {
+    let mut lock = TWIDDLES.lock().unwrap();
+    lock.clear();
+}
+
+/// Forward NTT of each consecutive `size` chunk of `values`, in parallel.
+///
+/// Uses the radix-4 [`ntt_u64`] with shared twiddles, see the module
+/// documentation. [`super::ntt_batch`] calls this for the sizes the planner
+/// runs through the same kernel.
+///
+/// # Panics
+///
+/// Panics if `size` is not a power of two dividing the multiplicative group
+/// order, or does not divide the length of `values`.
+pub fn par_ntt(values: &mut [Field], size: usize) {
+    assert!(values.len().is_multiple_of(size));
+    let twiddles = twiddles(size);
+    values
+        .par_chunks_exact_mut(size)
+        .for_each(|values| radix_4(values, &twiddles));
+}
+
+/// Bit-reverses `values` and runs [`ntt_u64`] on them, with `twiddles` as
+/// returned by [`twiddles`].
+pub(super) fn radix_4(values: &mut [Field], twiddles: &[Field]) {
+    let size = values.len();
+    // Taken rather than borrowed: large permutations run on rayon, and a
+    // stolen job on this thread would find the buffer borrowed.
+    let mut scratch = SCRATCH.take();
+    scratch.clear();
+    scratch.extend_from_slice(values);
+    if size <= 1 << permutation::MAX_TABLE_LOG_SIZE {
+        let table = permutation::bit_reverse_table(size);
+        permute_wo_oop(&scratch, values, 1, |i| table[i]);
+    } else {
+        permute_wo_oop(&scratch, values, 1, permutation::bit_reverse(size));
+    }
+    if scratch.capacity() <= MAX_SCRATCH {
+        SCRATCH.set(scratch);
+    }
+
+    ntt_u64(Field::peel_slice_mut(values), Field::peel_slice(twiddles));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::naive, *};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_par_ntt() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 16, 256] {
+            let input = (0..size * 37).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            for chunk in expected.chunks_exact_mut(size) {
+                naive::ntt(chunk);
+            }
+            let mut values = input.clone();
+            par_ntt(&mut values, size);
+            assert_eq!(values, expected);
+
+            let serial = rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap();
+            let mut values = input;
+            serial.install(|| par_ntt(&mut values, size));
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    fn test_twiddles_shared() {
+        assert!(Arc::ptr_eq(&twiddles(64), &twiddles(64)));
+        let first = twiddles(32);
+        clear_twiddles();
+        assert!(!Arc::ptr_eq(&first, &twiddles(32)));
+    }
+}
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::*;
+    use crate::bench::rand_vec;
+    use criterion::{BenchmarkId, Criterion, Throughput};
+
+    pub fn group(criterion: &mut Criterion) {
+        bench_par_ntt(criterion, 1 << 10, 1 << 10);
+    }
+
+    /// Scaling with the number of threads.
+    fn bench_par_ntt(criterion: &mut Criterion, size: usize, count: usize) {
+        let mut values: Vec<Field> = rand_vec(size * count);
+        let mut group = criterion.benchmark_group("ntt/batch");
+        group.throughput(Throughput::Elements(values.len() as u64));
+        let max_threads = rayon::current_num_threads();
+        for threads in (0..).map(|i| 1 << i).take_while(|&t| t <= max_threads) {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            group.bench_function(BenchmarkId::new(format!("{size}"), threads), |bencher| {
+                bencher.iter(|| pool.install(|| par_ntt(&mut values, size)));
+            });
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::{strategy, Ntt};
+use crate::Field;
+use std::sync::Arc;
+
+/// Bluestein's algorithm, computing an NTT as a convolution with a chirp.
+///
+/// With `ψ` a primitive `2n`-th root and `ω = ψ²`, it uses
+/// `jm = (j² + m² - (m - j)²) / 2` to write
+///
+/// ```text
+/// X_m = ψ^(m²) Σ_j (x_j ψ^(j²)) ψ^(-(m - j)²)
+/// ```
+///
+/// which is a cyclic convolution of size `M >= 2n - 1`, a power of two here.
+///
+/// There is no NTT of length `n` unless `n` divides `p - 1`, and all of those
+/// sizes are also handled by [`super::strategy`]. So this is only useful where
+/// a convolution of power-of-two size is preferable to the mixed radix
+/// strategies.
+///
+/// The plan caches the chirp, the transformed convolution kernel and a scratch
+/// buffer, so repeated transforms of the same size avoid recomputing and
+/// reallocating them.
+pub struct BluesteinPlan {
+    size:    usize,
+    chirp:   Vec<Field>,
+    kernel:  Vec<Field>,
+    inner:   Arc<dyn Ntt>,
+    scratch: Vec<Field>,
+}
+
+impl BluesteinPlan {
+    /// # Panics
+    ///
+    /// Panics if `2 · size` does not divide the multiplicative group order.
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0);
+        let psi = Field::root(2 * size as u64)
+            .expect("Twice the size must divide the multiplicative group order.");
+        let conv_size = (2 * size - 1).next_power_of_two();
+
+        // Chirp ψ^(k²), using (k + 1)² = k² + 2k + 1.
+        let mut chirp = Vec::with_capacity(size);
+        let mut value = Field::from(1);
+        let mut step = psi;
+        let psi_squared = psi * psi;
+        for _ in 0..size {
+            chirp.push(value);
+            value *= step;
+            step *= psi_squared;
+        }
+
+        // Kernel ψ^(-k²) for |k| < size, with the inverse NTT scaling.
+        let scale = Field::from(conv_size as u64).inv();
+        let mut kernel = vec![Field::from(0); conv_size];
+        for (k, &c) in chirp.iter().enumerate() {
+            let value = c.inv() * scale;
+            kernel[k] = value;
+            kernel[(conv_size - k) % conv_size] = value;
+        }
+        let inner = strategy(conv_size);
+        inner.ntt(&mut kernel);
+
+        Self {
+            size,
+            chirp,
+            kernel,
+            inner,
+            scratch: vec![Field::from(0); conv_size],
+        }
+    }
+
+    /// Forward NTT, the same as [`super::ntt`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` does not have the planned length.
+    pub fn forward(&mut self, values: &mut [Field]) {
+        assert_eq!(values.len(), self.size);
+        let scratch = &mut self.scratch;
+        for ((s, &x), &c) in scratch.iter_mut().zip(values.iter()).zip(&self.chirp) {
+            *s = x * c;
+        }
+        scratch[self.size..].fill(Field::from(0));
+
+        // Cyclic convolution with the kernel, scaling is in the kernel.
+        self.inner.ntt(scratch);
+        for (s, &k) in scratch.iter_mut().zip(&self.kernel) {
+            *s *= k;
+        }
+        scratch[1..].reverse();
+        self.inner.ntt(scratch);
+
+        for ((x, &s), &c) in values.iter_mut().zip(scratch.iter()).zip(&self.chirp) {
+            *x = s * c;
+        }
+    }
+
+    /// Inverse NTT, the same as [`super::intt`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` does not have the planned length.
+    pub fn inverse(&mut self, values: &mut [Field]) {
+        assert_eq!(values.len(), self.size);
+        let scale = Field::from(self.size as u64).inv();
+        for x in values.iter_mut() {
+            *x *= scale;
+        }
+        values[1..].reverse();
+        self.forward(values);
+    }
+}
+
+/// One-shot Bluestein NTT, see [`BluesteinPlan`].
+///
+/// # Panics
+///
+/// Panics if `2 · values.len()` does not divide the multiplicative group order.
+pub fn bluestein(values: &mut [Field]) {
+    if values.is_empty() {
+        return;
+    }
+    BluesteinPlan::new(values.len()).forward(values);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::naive, *};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_bluestein() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 3, 5, 12, 17, 60, 1020] {
+            let mut plan = BluesteinPlan::new(size);
+            for _ in 0..2 {
+                let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+                let mut expected = input.clone();
+                naive::ntt(&mut expected);
+
+                let mut one_shot = input.clone();
+                bluestein(&mut one_shot);
+                assert_eq!(one_shot, expected);
+
+                let mut values = input.clone();
+                plan.forward(&mut values);
+                assert_eq!(values, expected);
+                plan.inverse(&mut values);
+                assert_eq!(values, input);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Twice the size must divide the multiplicative group order.")]
+    fn test_unsupported_size() {
+        // Without an 11-th root of unity there is no transform to compute.
+        assert!(Field::root(11).is_none());
+        let _ = BluesteinPlan::new(11);
+    }
+}
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::*;
+    use crate::bench::rand_vec;
+    use criterion::{BenchmarkId, Criterion, Throughput};
+
+    pub fn group(criterion: &mut Criterion) {
+        // 1000 does not divide p - 1, 1020 = 4 · 3 · 5 · 17 is the closest.
+        bench_bluestein(criterion, 1020);
+    }
+
+    fn bench_bluestein(criterion: &mut Criterion, size: usize) {
+        let mut values: Vec<Field> = rand_vec(size);
+        let mut group = criterion.benchmark_group("ntt/bluestein");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("one_shot", size), |bencher| {
+            bencher.iter(|| bluestein(&mut values));
+        });
+        let mut plan = BluesteinPlan::new(size);
+        group.bench_function(BenchmarkId::new("plan", size), |bencher| {
+            bencher.iter(|| plan.forward(&mut values));
+        });
+    }
+}
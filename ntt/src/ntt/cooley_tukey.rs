@@ -1,5 +1,9 @@
-use super::{Ntt, MIN_WORK_SIZE};
+use super::{batch, Ntt, PlanObjective, MIN_WORK_SIZE};
 use crate::{
+    field::algo::{
+        butterflies, butterflies_4,
+        generic::{add, mont_reduce_128, sub},
+    },
     permute::{self, Permute},
     utils::div_round_up,
     Field,
@@ -8,34 +12,48 @@ use rayon::prelude::*;
 use std::{cmp, sync::Arc};
 
 pub struct CooleyTukey {
-    size:         usize,
-    split:        (usize, usize),
-    inner_a:      Arc<dyn Ntt>,
-    inner_b:      Arc<dyn Ntt>,
-    transpose_ab: Arc<dyn Permute<Field>>,
-    transpose_ba: Arc<dyn Permute<Field>>,
-    root:         Field,
-    twiddles:     Vec<Field>,
-    a_work_size:  usize,
-    b_work_size:  usize,
+    size:             usize,
+    split:            (usize, usize),
+    inner_a:          Arc<dyn Ntt>,
+    inner_b:          Arc<dyn Ntt>,
+    transpose_ab:     Arc<dyn Permute<Field>>,
+    transpose_ba:     Arc<dyn Permute<Field>>,
+    root:             Field,
+    twiddles:         Vec<Field>,
+    inverse_root:     Field,
+    // `twiddles` for `inverse_root`, used by `Ntt::intt`.
+    inverse_twiddles: Vec<Field>,
+    a_work_size:      usize,
+    b_work_size:      usize,
 }
 
 impl CooleyTukey {
-    #[allow(clippy::similar_names)] // TODO
+    #[cfg(any(test, feature = "bench"))]
     pub fn new(a: usize, b: usize) -> Self {
+        Self::with_objective(a, b, PlanObjective::default())
+    }
+
+    /// Construct with inner transforms planned for `objective`.
+    #[allow(clippy::similar_names)] // TODO
+    pub fn with_objective(a: usize, b: usize, objective: PlanObjective) -> Self {
         // eprintln!("CooleyTukey({a} x {b})");
         let size = a * b;
-        let inner_a = super::strategy(a);
-        let inner_b = super::strategy(b);
+        let inner_a = super::plan(a, objective);
+        let inner_b = super::plan(b, objective);
         let transpose_ab = permute::transpose_strategy((a, b));
         let transpose_ba = permute::transpose_strategy((b, a));
 
         // Compute twiddles
         let root = Field::root(size as u64).unwrap();
 
+        let inverse_root = root.inv();
+
         // Precompute twiddles for medium sized NTTs.
         // (For large ones we just compute them on the fly.)
-        let twiddles = if size <= 1 << 15 {
+        let twiddles = |root: Field| {
+            if size > 1 << 15 {
+                return Vec::new();
+            }
             let mut twiddles = Vec::with_capacity(size);
             let mut omega_b = Field::new(1);
             for _ in 0..b {
@@ -47,8 +65,6 @@ impl CooleyTukey {
                 omega_b *= root;
             }
             twiddles
-        } else {
-            Vec::new()
         };
 
         // Compute (parallel) work sizes
@@ -65,7 +81,9 @@ impl CooleyTukey {
             transpose_ab,
             transpose_ba,
             root,
-            twiddles,
+            twiddles: twiddles(root),
+            inverse_root,
+            inverse_twiddles: twiddles(inverse_root),
             a_work_size,
             b_work_size,
         }
@@ -78,7 +96,31 @@ impl Ntt for CooleyTukey {
     }
 
     fn ntt(&self, values: &mut [Field]) {
+        self.transform(values, false);
+    }
+
+    /// The forward algorithm with inverse roots and inner transforms. This
+    /// avoids reversing the whole input as the default does.
+    fn intt(&self, values: &mut [Field]) {
+        self.transform(values, true);
+    }
+}
+
+impl CooleyTukey {
+    fn transform(&self, values: &mut [Field], inverse: bool) {
         debug_assert_eq!(values.len() % self.size, 0);
+        let (root, twiddles) = if inverse {
+            (self.inverse_root, &self.inverse_twiddles)
+        } else {
+            (self.root, &self.twiddles)
+        };
+        let inner = |ntt: &Arc<dyn Ntt>, block: &mut [Field]| {
+            if inverse {
+                ntt.intt(block);
+            } else {
+                ntt.ntt(block);
+            }
+        };
         for values in values.chunks_exact_mut(self.size) {
             let (a, _) = self.split;
 
@@ -86,14 +128,14 @@ impl Ntt for CooleyTukey {
             self.transpose_ab.permute(values);
 
             // Run `b` NTTs of size `a` and apply twiddles
-            if !self.twiddles.is_empty() {
+            if !twiddles.is_empty() {
                 values
                     .par_chunks_mut(self.a_work_size)
-                    .zip(self.twiddles.par_chunks(self.a_work_size))
+                    .zip(twiddles.par_chunks(self.a_work_size))
                     .enumerate()
                     .for_each(
                         |(i, (mut block, mut twiddles)): (usize, (&mut [Field], &[Field]))| {
-                            self.inner_a.ntt(block);
+                            inner(&self.inner_a, block);
 
                             // Apply twiddles to the block
                             if i == 0 {
@@ -114,17 +156,17 @@ impl Ntt for CooleyTukey {
                 values
                     .par_chunks_mut(self.a_work_size)
                     .enumerate()
-                    .for_each(|(mut i, mut block)| {
-                        self.inner_a.ntt(block);
+                    .for_each(|(i, mut block)| {
+                        inner(&self.inner_a, block);
 
                         // Compute and apply twiddles.
-                        if i == 0 {
+                        let mut first_row = i * self.a_work_size / a;
+                        if first_row == 0 {
                             // Skip first row
                             block = &mut block[a..];
-                            i += 1;
+                            first_row = 1;
                         }
-                        let first_row = i * self.a_work_size / a;
-                        let mut omega_col = self.root.pow(first_row as u64);
+                        let mut omega_col = root.pow(first_row as u64);
                         for row in block.chunks_exact_mut(a) {
                             let mut omega_row = omega_col;
                             for value in row[1..a - 1].iter_mut() {
@@ -132,7 +174,7 @@ impl Ntt for CooleyTukey {
                                 omega_row *= omega_col;
                             }
                             row[a - 1] *= omega_row;
-                            omega_col *= self.root;
+                            omega_col *= root;
                         }
                     });
             }
@@ -141,17 +183,211 @@ impl Ntt for CooleyTukey {
 
             values
                 .par_chunks_mut(self.b_work_size)
-                .for_each(|block| self.inner_b.ntt(block));
+                .for_each(|block| inner(&self.inner_b, block));
 
             self.transpose_ab.permute(values);
         }
     }
 }
 
+/// Power-of-two transform by the radix-4 [`ntt_u64`] on each chunk.
+pub struct Radix4 {
+    size:             usize,
+    twiddles:         Arc<[Field]>,
+    // `twiddles` for the inverse root, used by `Ntt::intt`.
+    inverse_twiddles: Vec<Field>,
+}
+
+impl Radix4 {
+    pub fn new(size: usize) -> Self {
+        let twiddles = batch::twiddles(size);
+        // `ω^(-j) = -ω^(size / 2 - j)` for `0 < j < size / 2`.
+        let inverse_twiddles = (0..twiddles.len())
+            .map(|j| {
+                if j == 0 {
+                    Field::from(1)
+                } else {
+                    -twiddles[twiddles.len() - j]
+                }
+            })
+            .collect();
+        Self {
+            size,
+            twiddles,
+            inverse_twiddles,
+        }
+    }
+}
+
+impl Ntt for Radix4 {
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    fn ntt(&self, values: &mut [Field]) {
+        for values in values.chunks_exact_mut(self.size) {
+            batch::radix_4(values, &self.twiddles);
+        }
+    }
+
+    /// The forward algorithm with the inverse twiddles.
+    fn intt(&self, values: &mut [Field]) {
+        for values in values.chunks_exact_mut(self.size) {
+            batch::radix_4(values, &self.inverse_twiddles);
+        }
+    }
+}
+
+/// Smallest butterfly span in [`ntt_u64`] handed to the vector kernels;
+/// shorter stages stay in the scalar loops.
+const SIMD_MIN_HALF: usize = 8;
+
+/// Radix-4 decimation-in-time NTT on raw Montgomery representations.
+///
+/// Expects `values` in bit-reversed order and `twiddles[j] = ω^j` for
+/// `j < n / 2`, also raw. The output is in natural order. Working on `u64`
+/// directly keeps the butterfly loop free of `Field` wrapping, with the
+/// reduction inlined.
+///
+/// Pairs of radix-2 stages are fused into radix-4 passes, halving the passes
+/// over memory. For an odd power of two the last stage is radix 2, where its
+/// span is wide enough for the vector butterflies.
+pub fn ntt_u64(values: &mut [u64], twiddles: &[u64]) {
+    let size = values.len();
+    debug_assert!(size.is_power_of_two());
+    debug_assert_eq!(twiddles.len(), size / 2);
+    let mut scratch = (Vec::new(), Vec::new());
+    let mut half = 1;
+    while 4 * half <= size {
+        radix_4_stage(values, twiddles, half, &mut scratch);
+        half *= 4;
+    }
+    if half < size {
+        radix_2_stage(values, twiddles, half, &mut scratch.0);
+    }
+}
+
+/// Radix-2 version of [`ntt_u64`], one pass per stage.
+#[cfg(any(test, feature = "bench"))]
+pub fn ntt_u64_radix_2(values: &mut [u64], twiddles: &[u64]) {
+    let size = values.len();
+    debug_assert!(size.is_power_of_two());
+    debug_assert_eq!(twiddles.len(), size / 2);
+    let mut stage_twiddles = Vec::new();
+    let mut half = 1;
+    while half < size {
+        radix_2_stage(values, twiddles, half, &mut stage_twiddles);
+        half *= 2;
+    }
+}
+
+/// The twiddles `ω_{2·half}^j` for `j < half`, borrowed from `twiddles` when
+/// contiguous and gathered into `scratch` otherwise.
+fn stage_twiddles<'a>(twiddles: &'a [u64], half: usize, scratch: &'a mut Vec<u64>) -> &'a [u64] {
+    let step = 2 * twiddles.len() / (2 * half);
+    if step == 1 {
+        twiddles
+    } else {
+        scratch.clear();
+        scratch.extend(twiddles.iter().step_by(step).take(half));
+        scratch
+    }
+}
+
+/// The radix-2 stage with butterfly span `half`.
+fn radix_2_stage(values: &mut [u64], twiddles: &[u64], half: usize, scratch: &mut Vec<u64>) {
+    let step = values.len() / (2 * half);
+    if half < SIMD_MIN_HALF {
+        for chunk in values.chunks_exact_mut(2 * half) {
+            let (low, high) = chunk.split_at_mut(half);
+            for (j, (a, b)) in low.iter_mut().zip(high).enumerate() {
+                let t = mont_reduce_128(u128::from(*b) * u128::from(twiddles[j * step]));
+                *b = sub(*a, t);
+                *a = add(*a, t);
+            }
+        }
+    } else {
+        // The vector butterflies need the stage's twiddles contiguous.
+        let stage = stage_twiddles(twiddles, half, scratch);
+        for chunk in values.chunks_exact_mut(2 * half) {
+            let (low, high) = chunk.split_at_mut(half);
+            butterflies(low, high, stage);
+        }
+    }
+}
+
+/// The radix-2 stages with spans `half` and `2 · half`, fused.
+fn radix_4_stage(
+    values: &mut [u64],
+    twiddles: &[u64],
+    half: usize,
+    scratch: &mut (Vec<u64>, Vec<u64>),
+) {
+    if half < SIMD_MIN_HALF / 2 {
+        let step = values.len() / (4 * half);
+        let mul = |a: u64, b: u64| mont_reduce_128(u128::from(a) * u128::from(b));
+        for chunk in values.chunks_exact_mut(4 * half) {
+            for j in 0..half {
+                let (w1, w2, w3) = (
+                    twiddles[2 * j * step],
+                    twiddles[j * step],
+                    twiddles[(j + half) * step],
+                );
+                let t1 = mul(chunk[half + j], w1);
+                let (b0, b1) = (add(chunk[j], t1), sub(chunk[j], t1));
+                let t3 = mul(chunk[3 * half + j], w1);
+                let (b2, b3) = (add(chunk[2 * half + j], t3), sub(chunk[2 * half + j], t3));
+                let (u, v) = (mul(b2, w2), mul(b3, w3));
+                chunk[j] = add(b0, u);
+                chunk[2 * half + j] = sub(b0, u);
+                chunk[half + j] = add(b1, v);
+                chunk[3 * half + j] = sub(b1, v);
+            }
+        }
+    } else {
+        let w1 = stage_twiddles(twiddles, half, &mut scratch.0);
+        let w2 = stage_twiddles(twiddles, 2 * half, &mut scratch.1);
+        for chunk in values.chunks_exact_mut(4 * half) {
+            butterflies_4(chunk, w1, w2);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{super::tests::test_ntt, *};
 
+    #[test]
+    fn test_ntt_u64() {
+        use super::super::{batch::twiddles, naive};
+        use bytemuck::TransparentWrapper;
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 4, 8, 16, 32, 64, 256, 1 << 11, 1 << 12] {
+            let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            naive::ntt(&mut expected);
+            let twiddles = twiddles(size);
+            for ntt in [ntt_u64, ntt_u64_radix_2] {
+                let mut values = input.clone();
+                permute::bit_reverse(&mut values);
+                ntt(
+                    Field::peel_slice_mut(&mut values),
+                    Field::peel_slice(&twiddles),
+                );
+                assert_eq!(values, expected, "size {size}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_radix_4() {
+        for size in [1, 2, 64, 128, 1 << 11] {
+            test_ntt(Radix4::new(size));
+        }
+    }
+
     #[test]
     fn test_4x4() {
         test_ntt(CooleyTukey::new(4, 4));
@@ -176,16 +412,84 @@ mod tests {
     fn test_4x64() {
         test_ntt(CooleyTukey::new(16, 24));
     }
+
+    #[test]
+    fn test_256x256() {
+        // Too large for the precomputed twiddles and for the naive reference.
+        let ntt = CooleyTukey::new(256, 256);
+        let input = (0..1 << 16).map(Field::from).collect::<Vec<_>>();
+        let mut expected = input.clone();
+        super::super::split_radix::ntt(&mut expected);
+        let mut values = input.clone();
+        ntt.ntt(&mut values);
+        assert_eq!(values, expected);
+        ntt.intt(&mut values);
+        super::super::intt(&mut expected);
+        let size = Field::from(1 << 16);
+        assert!(values.iter().zip(&input).all(|(&x, &y)| x == y * size));
+        assert_eq!(expected, input);
+    }
 }
 
 #[cfg(feature = "bench")]
 #[doc(hidden)]
 pub mod bench {
-    use super::{super::bench::bench_ntt, *};
-    use criterion::Criterion;
+    use super::{
+        super::{batch::twiddles, bench::bench_ntt},
+        *,
+    };
+    use crate::bench::rand_vec;
+    use bytemuck::TransparentWrapper;
+    use criterion::{BenchmarkId, Criterion, Throughput};
 
     pub fn group(criterion: &mut Criterion) {
+        bench_ntt_u64(criterion, 1 << 16);
+        bench_ntt_u64(criterion, 1 << 20);
         bench_ntt(criterion, "cooley_tukey", CooleyTukey::new(16, 16));
         bench_ntt(criterion, "cooley_tukey", CooleyTukey::new(256, 257));
     }
+
+    /// The butterflies of [`ntt_u64`] written with `Field` arithmetic.
+    fn ntt_field(values: &mut [Field], twiddles: &[Field]) {
+        let size = values.len();
+        let mut half = 1;
+        while half < size {
+            let step = size / (2 * half);
+            for chunk in values.chunks_exact_mut(2 * half) {
+                let (low, high) = chunk.split_at_mut(half);
+                for (j, (a, b)) in low.iter_mut().zip(high).enumerate() {
+                    let t = *b * twiddles[j * step];
+                    *b = *a - t;
+                    *a += t;
+                }
+            }
+            half *= 2;
+        }
+    }
+
+    fn bench_ntt_u64(criterion: &mut Criterion, size: usize) {
+        let mut values: Vec<Field> = rand_vec(size);
+        let twiddles = twiddles(size);
+        let mut group = criterion.benchmark_group("ntt/radix_2");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("field", size), |bencher| {
+            bencher.iter(|| ntt_field(&mut values, &twiddles));
+        });
+        group.bench_function(BenchmarkId::new("u64", size), |bencher| {
+            bencher.iter(|| {
+                ntt_u64_radix_2(
+                    Field::peel_slice_mut(&mut values),
+                    Field::peel_slice(&twiddles),
+                );
+            });
+        });
+        group.bench_function(BenchmarkId::new("u64_radix_4", size), |bencher| {
+            bencher.iter(|| {
+                ntt_u64(
+                    Field::peel_slice_mut(&mut values),
+                    Field::peel_slice(&twiddles),
+                );
+            });
+        });
+    }
 }
@@ -0,0 +1,152 @@
+//! Radix-2 NTTs with bit-reversed spectra.
+//!
+//! The forward transform is decimation-in-frequency (Gentleman-Sande), taking
+//! natural order input to bit-reversed output. The inverse is
+//! decimation-in-time, taking bit-reversed input back to natural order.
+//! Pointwise operations like convolution do not care about the order of the
+//! spectrum, so pairing the two skips both bit-reversal permutations.
+
+use super::batch::twiddles;
+use crate::Field;
+
+/// Forward NTT of `values` with the output in bit-reversed order.
+///
+/// # Panics
+///
+/// Panics if the length is not a power of two dividing the multiplicative
+/// group order.
+pub fn ntt_dif(values: &mut [Field]) {
+    let size = values.len();
+    assert!(size.is_power_of_two());
+    let twiddles = twiddles(size);
+    let mut len = size;
+    while len >= 2 {
+        let half = len / 2;
+        let stride = size / len;
+        for block in values.chunks_exact_mut(len) {
+            let (low, high) = block.split_at_mut(half);
+            for (j, (a, b)) in low.iter_mut().zip(high).enumerate() {
+                let (x, y) = (*a, *b);
+                *a = x + y;
+                *b = (x - y) * twiddles[j * stride];
+            }
+        }
+        len = half;
+    }
+}
+
+/// Inverse NTT of bit-reversed `values`, as produced by [`ntt_dif`], with the
+/// output in natural order. Includes the `1 / len` scaling.
+///
+/// # Panics
+///
+/// Panics if the length is not a power of two dividing the multiplicative
+/// group order.
+pub fn intt_dit(values: &mut [Field]) {
+    let size = values.len();
+    assert!(size.is_power_of_two());
+    let twiddles = twiddles(size);
+    // `ω^(-j) = -ω^(size / 2 - j)` for `0 < j < size / 2`.
+    let inverse_twiddle = |j: usize| {
+        if j == 0 {
+            Field::from(1)
+        } else {
+            -twiddles[size / 2 - j]
+        }
+    };
+    let mut len = 2;
+    while len <= size {
+        let half = len / 2;
+        let stride = size / len;
+        for block in values.chunks_exact_mut(len) {
+            let (low, high) = block.split_at_mut(half);
+            for (j, (a, b)) in low.iter_mut().zip(high).enumerate() {
+                let (x, y) = (*a, *b * inverse_twiddle(j * stride));
+                *a = x + y;
+                *b = x - y;
+            }
+        }
+        len *= 2;
+    }
+    let scale = Field::from(size as u64).inv();
+    for value in values {
+        *value *= scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::naive, *};
+    use crate::{convolve::circular_naive, permute::permutation::bit_reverse};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_ntt_dif() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 4, 16, 256, 1024] {
+            let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            naive::ntt(&mut expected);
+            let mut values = input.clone();
+            ntt_dif(&mut values);
+            let reverse = bit_reverse(size);
+            for (i, &value) in values.iter().enumerate() {
+                assert_eq!(value, expected[reverse(i)]);
+            }
+            intt_dit(&mut values);
+            assert_eq!(values, input);
+        }
+    }
+
+    #[test]
+    fn test_convolution() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 8, 256] {
+            let a = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let b = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let expected = circular_naive(&a, &b);
+            let mut values = a;
+            let mut kernel = b;
+            ntt_dif(&mut values);
+            ntt_dif(&mut kernel);
+            for (value, kernel) in values.iter_mut().zip(&kernel) {
+                *value *= *kernel;
+            }
+            intt_dit(&mut values);
+            assert_eq!(values, expected);
+        }
+    }
+}
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::{
+        super::{intt, ntt},
+        *,
+    };
+    use crate::bench::rand_vec;
+    use criterion::{BenchmarkId, Criterion, Throughput};
+
+    pub fn group(criterion: &mut Criterion) {
+        bench_round_trip(criterion, 1 << 16);
+    }
+
+    fn bench_round_trip(criterion: &mut Criterion, size: usize) {
+        let mut values: Vec<Field> = rand_vec(size);
+        let mut group = criterion.benchmark_group("ntt/cooley_tukey_dif");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("dif_dit", size), |bencher| {
+            bencher.iter(|| {
+                ntt_dif(&mut values);
+                intt_dit(&mut values);
+            });
+        });
+        group.bench_function(BenchmarkId::new("ntt_intt", size), |bencher| {
+            bencher.iter(|| {
+                ntt(&mut values);
+                intt(&mut values);
+            });
+        });
+    }
+}
@@ -0,0 +1,114 @@
+//! Vectors tagged with their domain, to catch transforming data twice.
+
+use super::{intt, ntt};
+use crate::Field;
+
+/// Whether a [`DomainVec`] holds coefficients or evaluations.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Domain {
+    Coefficients,
+    Evaluations,
+}
+
+/// A vector of field elements tagged with its [`Domain`].
+///
+/// [`DomainVec::ntt`] and [`DomainVec::intt`] check the tag and flip it, so
+/// transforming in the wrong direction panics instead of silently producing
+/// garbage.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DomainVec {
+    data:   Vec<Field>,
+    domain: Domain,
+}
+
+impl DomainVec {
+    #[must_use]
+    pub const fn new(data: Vec<Field>, domain: Domain) -> Self {
+        Self { data, domain }
+    }
+
+    #[must_use]
+    pub const fn domain(&self) -> Domain {
+        self.domain
+    }
+
+    #[must_use]
+    pub fn data(&self) -> &[Field] {
+        &self.data
+    }
+
+    /// Mutable access to the values. The domain stays the same.
+    pub fn data_mut(&mut self) -> &mut [Field] {
+        &mut self.data
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> Vec<Field> {
+        self.data
+    }
+
+    /// Forward NTT from coefficients to evaluations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data is already in the evaluation domain.
+    pub fn ntt(&mut self) {
+        assert_eq!(
+            self.domain,
+            Domain::Coefficients,
+            "Forward NTT of data in the evaluation domain."
+        );
+        ntt(&mut self.data);
+        self.domain = Domain::Evaluations;
+    }
+
+    /// Inverse NTT from evaluations to coefficients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data is already in the coefficient domain.
+    pub fn intt(&mut self) {
+        assert_eq!(
+            self.domain,
+            Domain::Evaluations,
+            "Inverse NTT of data in the coefficient domain."
+        );
+        intt(&mut self.data);
+        self.domain = Domain::Coefficients;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitions() {
+        let coefficients = (1..=8).map(Field::from).collect::<Vec<_>>();
+        let mut expected = coefficients.clone();
+        ntt(&mut expected);
+
+        let mut values = DomainVec::new(coefficients.clone(), Domain::Coefficients);
+        values.ntt();
+        assert_eq!(values.domain(), Domain::Evaluations);
+        assert_eq!(values.data(), expected);
+        values.intt();
+        assert_eq!(values.domain(), Domain::Coefficients);
+        assert_eq!(values.into_inner(), coefficients);
+    }
+
+    #[test]
+    #[should_panic(expected = "Forward NTT of data in the evaluation domain")]
+    fn test_double_ntt() {
+        let mut values = DomainVec::new(vec![Field::from(1); 8], Domain::Coefficients);
+        values.ntt();
+        values.ntt();
+    }
+
+    #[test]
+    #[should_panic(expected = "Inverse NTT of data in the coefficient domain")]
+    fn test_intt_of_coefficients() {
+        let mut values = DomainVec::new(vec![Field::from(1); 8], Domain::Coefficients);
+        values.intt();
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! * <https://eng.libretexts.org/Bookshelves/Electrical_Engineering/Signal_Processing_and_Modeling/Fast_Fourier_Transforms_(Burrus)>
 //! * <https://www.youtube.com/watch?v=8cjDKirNIko>
-use super::{Ntt, MIN_WORK_SIZE};
+use super::{Ntt, PlanObjective, MIN_WORK_SIZE};
 use crate::{
     permute::{self, cycles, Permute},
     utils::{div_round_up, gcd, modinv},
@@ -23,7 +23,13 @@ pub struct GoodThomas {
 }
 
 impl GoodThomas {
+    #[cfg(any(test, feature = "bench"))]
     pub fn new(n1: usize, n2: usize) -> Self {
+        Self::with_objective(n1, n2, PlanObjective::default())
+    }
+
+    /// Construct with inner transforms planned for `objective`.
+    pub fn with_objective(n1: usize, n2: usize, objective: PlanObjective) -> Self {
         let n = n1 * n2;
         assert_eq!(
             gcd(n1, n2),
@@ -33,8 +39,8 @@ impl GoodThomas {
         // eprintln!("GoodThomas({n1} x {n2})");
 
         // Inner NTTs
-        let inner_n1 = super::strategy(n1);
-        let inner_n2 = super::strategy(n2);
+        let inner_n1 = super::plan(n1, objective);
+        let inner_n2 = super::plan(n2, objective);
 
         // Find permutation parameters
         // See C.S. Burrus (2018) eq. (10.5).
@@ -96,6 +102,53 @@ impl GoodThomas {
     }
 }
 
+/// The most balanced coprime split `n = a · b` with `1 < a < b`, or `None` if
+/// `n` is a prime power (or one) and has no such split.
+///
+/// The coprime splits group the prime power factors of `n` in two sets, this
+/// tries all of them.
+#[must_use]
+pub fn best_split(n: usize) -> Option<(usize, usize)> {
+    // Factor into prime powers by trial division.
+    let mut powers = Vec::new();
+    let mut rest = n;
+    let mut p = 2;
+    while p * p <= rest {
+        if rest.is_multiple_of(p) {
+            let mut power = 1;
+            while rest.is_multiple_of(p) {
+                power *= p;
+                rest /= p;
+            }
+            powers.push(power);
+        }
+        p += 1;
+    }
+    if rest > 1 {
+        powers.push(rest);
+    }
+    if powers.len() < 2 {
+        return None;
+    }
+
+    // The last factor is always in `b`, which gives each split once.
+    let others = &powers[..powers.len() - 1];
+    let mut best = 1;
+    for subset in 0..1_usize << others.len() {
+        let a = others
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| subset >> i & 1 == 1)
+            .map(|(_, &power)| power)
+            .product::<usize>();
+        let a = a.min(n / a);
+        if a > best {
+            best = a;
+        }
+    }
+    Some((best, n / best))
+}
+
 impl Ntt for GoodThomas {
     fn len(&self) -> usize {
         self.split.0 * self.split.1
@@ -130,6 +183,24 @@ impl Ntt for GoodThomas {
 mod tests {
     use super::{super::tests::test_ntt, *};
 
+    #[test]
+    fn test_best_split() {
+        assert_eq!(best_split(12), Some((3, 4)));
+        assert_eq!(best_split(35), Some((5, 7)));
+        assert_eq!(best_split(240), Some((15, 16)));
+        assert_eq!(best_split(65535), Some((255, 257)));
+        assert_eq!(best_split(1), None);
+        assert_eq!(best_split(17), None);
+        assert_eq!(best_split(1 << 20), None);
+        assert_eq!(best_split(3 * 3 * 3), None);
+        for n in [6, 60, 1020, 4080, 65535 * 16] {
+            let (a, b) = best_split(n).unwrap();
+            assert_eq!(a * b, n);
+            assert_eq!(gcd(a, b), 1);
+            assert!(1 < a && a < b);
+        }
+    }
+
     #[test]
     fn test_2x3() {
         test_ntt(GoodThomas::new(2, 3));
@@ -1,18 +1,42 @@
+pub mod batch;
+mod bluestein;
 mod cooley_tukey;
+pub mod cooley_tukey_dif;
+mod domain;
 mod good_thomas;
 pub mod gpu;
 pub mod naive;
+pub mod negacyclic;
+mod plan;
 mod rader;
+pub mod six_step;
 pub mod small;
+pub mod split_radix;
+pub mod stockham;
 
-use self::{cooley_tukey::CooleyTukey, good_thomas::GoodThomas, rader::Rader};
+pub use self::{
+    bluestein::{bluestein, BluesteinPlan},
+    domain::{Domain, DomainVec},
+    negacyclic::negacyclic_mul,
+    plan::{NttPlan, UnsupportedSize},
+};
+
+use self::{
+    cooley_tukey::{CooleyTukey, Radix4},
+    good_thomas::GoodThomas,
+    rader::Rader,
+};
 use crate::{
-    divisors::{is_divisor, split},
+    divisors::{divisors, is_divisor, split},
+    permute::{self, transpose},
     utils::gcd,
     Field,
 };
+use rayon::prelude::*;
 use std::{
     collections::BTreeMap,
+    mem::MaybeUninit,
+    ops::RangeInclusive,
     sync::{Arc, Mutex},
 };
 
@@ -20,7 +44,41 @@ use std::{
 // const MIN_WORK_SIZE: usize = 1 << 14;
 const MIN_WORK_SIZE: usize = 134217728 / 8;
 
-static CACHE: Mutex<BTreeMap<usize, Arc<dyn Ntt>>> = Mutex::new(BTreeMap::new());
+/// Minimum batch size (in elements) for parallelizing across vectors.
+const BATCH_PAR_THRESHOLD: usize = 1 << 14;
+
+/// Largest size handled by a single generated kernel.
+const SMALL_SIZE: usize = 128;
+
+/// Range of power-of-two sizes handled by the radix-4 kernel. Below it the
+/// splits into generated kernels are faster, above it the splits with
+/// cache-sized radix-4 transforms are.
+const RADIX_4_SIZES: RangeInclusive<usize> = (1 << 11)..=(1 << 22);
+
+static CACHE: Mutex<BTreeMap<(usize, PlanObjective), Arc<dyn Ntt>>> = Mutex::new(BTreeMap::new());
+
+/// What the planner optimizes for when decomposing a transform.
+///
+/// The planner uses a simple cost model. Sizes up to 128 are done by a single
+/// generated kernel, which counts as one pass over memory. A split `n = a · b`
+/// (Cooley-Tukey or Good-Thomas) costs three passes for its permutations plus
+/// the passes of the `a`- and `b`-sized transforms. Powers of two from `2^11`
+/// to `2^22` are not split at all but done by the radix-4 kernel.
+///
+/// Neither objective ever picks [`six_step::SixStep`] or [`split_radix`].
+/// These are only available by calling them directly.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum PlanObjective {
+    /// Minimize arithmetic. Splits are as balanced as possible and coprime
+    /// splits use Good-Thomas, which needs no twiddle multiplications. A
+    /// coprime split is preferred if its smaller factor is at least half that
+    /// of the most balanced split.
+    #[default]
+    MinOps,
+    /// Minimize the number of passes over memory in the above model. This
+    /// favors splits where one factor fits in a single kernel.
+    MinPasses,
+}
 
 pub trait Ntt: Sync + Send {
     fn len(&self) -> usize;
@@ -28,6 +86,17 @@ pub trait Ntt: Sync + Send {
     // OPT: We could also have the stride stored in the structure, and just pass raw
     // pointers down the tree.
     fn ntt(&self, values: &mut [Field]);
+
+    /// Inverse transform without the `1 / len` scaling.
+    ///
+    /// The default reverses all but the first element of each `len` chunk and
+    /// runs the forward transform.
+    fn intt(&self, values: &mut [Field]) {
+        for chunk in values.chunks_exact_mut(self.len()) {
+            chunk[1..].reverse();
+        }
+        self.ntt(values);
+    }
 }
 
 impl Ntt for Arc<dyn Ntt> {
@@ -38,16 +107,36 @@ impl Ntt for Arc<dyn Ntt> {
     fn ntt(&self, values: &mut [Field]) {
         self.as_ref().ntt(values)
     }
+
+    fn intt(&self, values: &mut [Field]) {
+        self.as_ref().intt(values);
+    }
 }
 
+/// Clear the cached plans, and the transpose strategies and twiddle tables
+/// they use.
 pub fn clear_cache() {
     let mut lock = CACHE.lock().unwrap();
     lock.clear();
+    drop(lock);
+    permute::clear_transpose_cache();
+    batch::clear_twiddles();
 }
 
+#[must_use]
 pub fn strategy(size: usize) -> Arc<dyn Ntt> {
+    plan(size, PlanObjective::default())
+}
+
+/// Plan a transform of the given size, optimizing for `objective`.
+///
+/// # Panics
+///
+/// Panics if `size` does not divide the multiplicative group order.
+#[must_use]
+pub fn plan(size: usize, objective: PlanObjective) -> Arc<dyn Ntt> {
     let lock = CACHE.lock().unwrap();
-    if let Some(ntt) = lock.get(&size) {
+    if let Some(ntt) = lock.get(&(size, objective)) {
         return ntt.clone();
     }
     drop(lock);
@@ -57,26 +146,116 @@ pub fn strategy(size: usize) -> Arc<dyn Ntt> {
         "{size} is not a supported NTT size (does not divide multiplicative order)"
     );
 
-    let ntt = if size <= 128 {
-        small::ntt(size).unwrap()
-    } else if size == 17 || size == 257 || size == 65537 {
-        Arc::new(Rader::new(size)) as Arc<dyn Ntt>
-    } else {
-        let a = split(size);
-        let b = size / a;
+    let ntt = if uses_radix_4(size) {
+        Arc::new(Radix4::new(size)) as Arc<dyn Ntt>
+    } else if let Some((a, b)) = plan_split(size, objective) {
         if gcd(a, b) == 1 {
-            Arc::new(GoodThomas::new(a, b)) as Arc<dyn Ntt>
+            Arc::new(GoodThomas::with_objective(a, b, objective)) as Arc<dyn Ntt>
         } else {
-            Arc::new(CooleyTukey::new(a, b)) as Arc<dyn Ntt>
+            Arc::new(CooleyTukey::with_objective(a, b, objective)) as Arc<dyn Ntt>
         }
+    } else if size <= SMALL_SIZE {
+        small::ntt(size).unwrap()
+    } else {
+        Arc::new(Rader::new(size)) as Arc<dyn Ntt>
     };
     assert_eq!(ntt.len(), size);
 
     let mut lock = CACHE.lock().unwrap();
-    lock.insert(size, ntt.clone());
+    lock.insert((size, objective), ntt.clone());
     ntt
 }
 
+/// The top level split `(a, b)` with `a <= b` the planner uses for `size`.
+///
+/// Returns `None` if the size is handled directly by a small kernel, the
+/// radix-4 kernel or Rader's algorithm.
+///
+/// # Panics
+///
+/// Panics if `size` does not divide the multiplicative group order.
+#[must_use]
+pub fn plan_split(size: usize, objective: PlanObjective) -> Option<(usize, usize)> {
+    if size <= SMALL_SIZE || uses_radix_4(size) || rader::PRIMES.contains(&size) {
+        return None;
+    }
+    let a = match objective {
+        PlanObjective::MinOps => {
+            let a = split(size);
+            match good_thomas::best_split(size) {
+                Some((coprime, _)) if 2 * coprime >= a => coprime,
+                _ => a,
+            }
+        }
+        PlanObjective::MinPasses => {
+            let mut memo = BTreeMap::new();
+            min_passes(size, &mut memo).1
+        }
+    };
+    Some((a, size / a))
+}
+
+/// The sizes of the leaf transforms the default planner decomposes `size`
+/// into, in depth-first order. Their product is `size`.
+///
+/// Splits into coprime factors are done by Good-Thomas, which needs no
+/// twiddle multiplications, others by Cooley-Tukey. A leaf is either a small
+/// kernel, a power of two handled by the radix-4 kernel or a prime handled by
+/// Rader's algorithm.
+///
+/// # Panics
+///
+/// Panics if `size` does not divide the multiplicative group order.
+#[must_use]
+pub fn best_factorization(size: usize) -> Vec<usize> {
+    match plan_split(size, PlanObjective::default()) {
+        Some((a, b)) => {
+            let mut factors = best_factorization(a);
+            factors.extend(best_factorization(b));
+            factors
+        }
+        None => vec![size],
+    }
+}
+
+/// Whether the planner runs `size` as a single radix-4 transform.
+fn uses_radix_4(size: usize) -> bool {
+    size.is_power_of_two() && RADIX_4_SIZES.contains(&size)
+}
+
+/// Returns the number of passes and the best split factor for `size` in the
+/// [`PlanObjective`] cost model. Among equally good splits the most balanced
+/// one is picked.
+fn min_passes(size: usize, memo: &mut BTreeMap<usize, (usize, usize)>) -> (usize, usize) {
+    if size <= SMALL_SIZE {
+        return (1, 1);
+    }
+    if let Some(&result) = memo.get(&size) {
+        return result;
+    }
+    let mut best = (usize::MAX, 1);
+    for &d in divisors() {
+        let Ok(a) = usize::try_from(d) else { break };
+        let b = size / a;
+        if a > b {
+            break;
+        }
+        if a == 1 || !size.is_multiple_of(a) {
+            continue;
+        }
+        let passes = 3 + min_passes(a, memo).0 + min_passes(b, memo).0;
+        if passes <= best.0 {
+            best = (passes, a);
+        }
+    }
+    if best.0 == usize::MAX {
+        // No non-trivial split, this is one of the large primes.
+        best = (3 + 2 * min_passes(size - 1, memo).0, 1);
+    }
+    memo.insert(size, best);
+    best
+}
+
 pub struct NttFn<F: Fn(&mut [Field]) + Send + Sync>(usize, F);
 
 impl<F: Fn(&mut [Field]) + Send + Sync> NttFn<F> {
@@ -95,12 +274,477 @@ impl<F: Fn(&mut [Field]) + Send + Sync> Ntt for NttFn<F> {
     }
 }
 
+/// Forward NTT of `values`.
+///
+/// With the `paranoid` feature in debug builds, this panics if any of the
+/// inputs is not canonical.
 pub fn ntt(values: &mut [Field]) {
+    check_canonical(values);
     let strat = strategy(values.len());
     strat.ntt(values);
     return;
 }
 
+/// With the `paranoid` feature in debug builds, panics if any of `values` is
+/// not canonical.
+#[inline(always)]
+fn check_canonical(values: &[Field]) {
+    if cfg!(all(debug_assertions, feature = "paranoid")) {
+        if let Some(index) = values.iter().position(|value| !value.is_canonical()) {
+            panic!("NTT input {index} is not canonical");
+        }
+    }
+}
+
+/// Forward NTT of `values` with all parallel work done in `pool` instead of
+/// the global `rayon` pool.
+///
+/// The parallelization thresholds are unchanged: transforms with less than
+/// `MIN_WORK_SIZE` elements per parallel block still run sequentially, only
+/// now on a thread of `pool`. So a one-thread pool makes every transform
+/// sequential, and larger pools only help for large transforms.
+pub fn ntt_in_pool(values: &mut [Field], pool: &rayon::ThreadPool) {
+    pool.install(|| ntt(values));
+}
+
+/// Out-of-place forward NTT of `input` into a possibly uninitialized
+/// `output`, returning `output` as initialized values.
+///
+/// Every element of `output` is written before it is read, so large output
+/// buffers need not be zeroed first, e.g. use [`Vec::spare_capacity_mut`]
+/// and then set the length.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths or the length does
+/// not divide the multiplicative group order.
+pub fn ntt_into_uninit<'a>(
+    input: &[Field],
+    output: &'a mut [MaybeUninit<Field>],
+) -> &'a mut [Field] {
+    assert_eq!(input.len(), output.len());
+    for (output, &input) in output.iter_mut().zip(input) {
+        output.write(input);
+    }
+    // Safety: all elements were written above and `MaybeUninit<Field>` has
+    // the same layout as `Field`.
+    let output = unsafe { &mut *(std::ptr::from_mut(output) as *mut [Field]) };
+    ntt(output);
+    output
+}
+
+/// Forward NTT of the length `size` vector with the given `(index, coeff)`
+/// entries and zeros elsewhere.
+///
+/// Repeated indices are added up.
+///
+/// # Panics
+///
+/// Panics if an index is not less than `size` or `size` does not divide the
+/// multiplicative group order.
+#[must_use]
+pub fn ntt_from_iter(iter: impl Iterator<Item = (usize, Field)>, size: usize) -> Vec<Field> {
+    let mut values = vec![Field::from(0); size];
+    for (index, coeff) in iter {
+        assert!(index < size, "index {index} out of range for size {size}");
+        values[index] += coeff;
+    }
+    ntt(&mut values);
+    values
+}
+
+/// Deterministic test vector of length `size` and its NTT, computed with
+/// [`naive::ntt`].
+///
+/// The input is generated by SplitMix64 seeded with `seed`, keeping the
+/// outputs less than the modulus as canonical values in order. This is simple
+/// to reproduce in other implementations for byte-for-byte comparisons.
+///
+/// # Panics
+///
+/// Panics if `size` does not divide the multiplicative group order.
+#[must_use]
+pub fn reference_vector(seed: u64, size: usize) -> (Vec<Field>, Vec<Field>) {
+    let mut state = seed;
+    let mut splitmix = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+    let input = std::iter::repeat_with(&mut splitmix)
+        .filter(|&x| x < Field::MODULUS)
+        .take(size)
+        .map(Field::from)
+        .collect::<Vec<_>>();
+    let mut output = input.clone();
+    naive::ntt(&mut output);
+    (input, output)
+}
+
+/// Forward NTT of each consecutive `size` chunk of `values`, the same as
+/// calling [`ntt`] on each chunk.
+///
+/// All chunks share one strategy and its twiddles. Batches of at least
+/// `BATCH_PAR_THRESHOLD` elements are transformed in parallel across chunks.
+/// Sizes run by the radix-4 kernel go to [`batch::par_ntt`].
+///
+/// # Panics
+///
+/// Panics if `size` is zero, does not divide the length of `values` or does
+/// not divide the multiplicative group order.
+pub fn ntt_batch(values: &mut [Field], size: usize) {
+    assert!(size > 0 && values.len().is_multiple_of(size));
+    if uses_radix_4(size) {
+        batch::par_ntt(values, size);
+        return;
+    }
+    let strat = strategy(size);
+    if values.len() < BATCH_PAR_THRESHOLD {
+        for chunk in values.chunks_exact_mut(size) {
+            strat.ntt(chunk);
+        }
+    } else {
+        values
+            .par_chunks_exact_mut(size)
+            .for_each(|chunk| strat.ntt(chunk));
+    }
+}
+
+/// Inverse NTT of each consecutive `size` chunk of `values`, the same as
+/// calling [`intt`] on each chunk.
+///
+/// # Panics
+///
+/// Panics if `size` is zero, does not divide the length of `values` or does
+/// not divide the multiplicative group order.
+pub fn intt_batch(values: &mut [Field], size: usize) {
+    assert!(size > 0 && values.len().is_multiple_of(size));
+    let strat = strategy(size);
+    let scale = Field::from(size as u64).inv();
+    let intt = |chunk: &mut [Field]| {
+        for x in chunk.iter_mut() {
+            *x *= scale;
+        }
+        strat.intt(chunk);
+    };
+    if values.len() < BATCH_PAR_THRESHOLD {
+        values.chunks_exact_mut(size).for_each(intt);
+    } else {
+        values.par_chunks_exact_mut(size).for_each(intt);
+    }
+}
+
+/// Two-dimensional NTT of a row-major `rows × cols` matrix in place.
+///
+/// Transforms each row, then each column by transposing, transforming the
+/// rows of the transpose and transposing back.
+///
+/// # Panics
+///
+/// Panics if `values.len()` does not equal `rows * cols` or if either
+/// dimension does not divide the multiplicative group order.
+pub fn ntt2d(values: &mut [Field], (rows, cols): (usize, usize)) {
+    assert_eq!(
+        values.len(),
+        rows * cols,
+        "Shape does not match the length."
+    );
+    if values.is_empty() {
+        return;
+    }
+    ntt_batch(values, cols);
+    ntt_columns(values, (rows, cols));
+}
+
+/// Forward NTT of each column of a row-major `rows × cols` matrix in place.
+///
+/// Transposes, transforms the rows of the transpose in parallel and
+/// transposes back. Only `rows` needs to be a supported NTT size. For a
+/// column-major matrix the columns are contiguous and [`ntt_batch`] does this
+/// without the transposes.
+///
+/// # Panics
+///
+/// Panics if `values.len()` does not equal `rows * cols` or if `rows` does
+/// not divide the multiplicative group order.
+pub fn ntt_columns(values: &mut [Field], (rows, cols): (usize, usize)) {
+    assert_eq!(
+        values.len(),
+        rows * cols,
+        "Shape does not match the length."
+    );
+    if values.is_empty() {
+        return;
+    }
+    transpose(values, (rows, cols));
+    ntt_batch(values, rows);
+    transpose(values, (cols, rows));
+}
+
+/// Inverse of [`ntt2d`], including the `1 / (rows · cols)` scaling.
+///
+/// # Panics
+///
+/// Panics if `values.len()` does not equal `rows * cols` or if either
+/// dimension does not divide the multiplicative group order.
+pub fn intt2d(values: &mut [Field], (rows, cols): (usize, usize)) {
+    assert_eq!(
+        values.len(),
+        rows * cols,
+        "Shape does not match the length."
+    );
+    if values.is_empty() {
+        return;
+    }
+    intt_batch(values, cols);
+    transpose(values, (rows, cols));
+    intt_batch(values, rows);
+    transpose(values, (cols, rows));
+}
+
+/// Shift a spectrum such that its inverse NTT is rotated right by `k`, i.e.
+/// the same as `values.rotate_right(k)` before the forward transform.
+///
+/// Multiplies `X_m` by `ω^(k·m)`.
+///
+/// # Panics
+///
+/// Panics if the length does not divide the multiplicative group order.
+pub fn time_shift(spectrum: &mut [Field], k: usize) {
+    if spectrum.is_empty() {
+        return;
+    }
+    let n = spectrum.len();
+    let root = Field::root(n as u64)
+        .expect("Vector length does not divide multiplicative group order.")
+        .pow((k % n) as u64);
+    let mut twiddle = Field::from(1);
+    for value in spectrum {
+        *value *= twiddle;
+        twiddle *= root;
+    }
+}
+
+/// The sum `Σ_i ω^(i·k)` over the subgroup of order `domain_size`, where `ω`
+/// is a primitive `domain_size`-th root of unity.
+///
+/// This is `domain_size` if `k` is a multiple of `domain_size` and zero
+/// otherwise.
+///
+/// # Panics
+///
+/// Panics if `domain_size` is zero.
+#[must_use]
+pub fn subgroup_power_sum(domain_size: usize, k: usize) -> Field {
+    assert!(domain_size > 0);
+    if k.is_multiple_of(domain_size) {
+        Field::from(domain_size as u64)
+    } else {
+        Field::from(0)
+    }
+}
+
+/// Forward NTT with fast paths for constant and single non-zero inputs.
+///
+/// A constant `c` transforms to `[N·c, 0, 0, ...]` and a single `c` at index
+/// `k` transforms to `c·ω^(i·k)`. Other inputs fall back to [`ntt`]. Checking
+/// for these costs a scan over the input, so this is only worth it where such
+/// inputs are common, e.g. for zero padded vectors. Like [`ntt`], this checks
+/// the inputs with the `paranoid` feature in debug builds.
+///
+/// # Panics
+///
+/// Panics if the length is not zero and does not divide the multiplicative
+/// group order.
+pub fn ntt_maybe_special(values: &mut [Field]) {
+    let Some(&first) = values.first() else {
+        return;
+    };
+    assert!(
+        is_divisor(values.len()),
+        "{} is not a supported NTT size (does not divide multiplicative order)",
+        values.len()
+    );
+    check_canonical(values);
+    if values.iter().all(|&value| value == first) {
+        values[0] = Field::from(values.len() as u64) * first;
+        values[1..].fill(Field::from(0));
+        return;
+    }
+    let mut non_zero = values
+        .iter()
+        .enumerate()
+        .filter(|(_, &value)| value != Field::from(0));
+    if let (Some((k, &value)), None) = (non_zero.next(), non_zero.next()) {
+        let root = Field::root(values.len() as u64)
+            .expect("Vector length does not divide multiplicative group order.")
+            .pow(k as u64);
+        let mut x = value;
+        for value in values.iter_mut() {
+            *value = x;
+            x *= root;
+        }
+        return;
+    }
+    ntt(values);
+}
+
+/// Forward NTT of `values` where only the first `nonzero_prefix` values can be
+/// non-zero, as for zero padded inputs.
+///
+/// With `N = r · m` and `m >= nonzero_prefix`, writing the output index as
+/// `i = d · r + c` gives
+///
+/// ```text
+/// X_(d·r + c) = Σ_(j < m) (x_j ω_N^(j·c)) ω_m^(j·d)
+/// ```
+///
+/// so the transform is `r` twisted copies of the input followed by length `m`
+/// NTTs, skipping the butterflies on the zeros. This picks the smallest such
+/// `m` and saves about a factor `log N / log m` of the work.
+///
+/// # Panics
+///
+/// Panics if `nonzero_prefix` exceeds the length or the length does not divide
+/// the multiplicative group order. In debug builds, panics if the values after
+/// the prefix are not zero.
+pub fn ntt_pruned(values: &mut [Field], nonzero_prefix: usize) {
+    let n = values.len();
+    assert!(nonzero_prefix <= n);
+    debug_assert!(
+        values[nonzero_prefix..]
+            .iter()
+            .all(|&value| value == Field::from(0)),
+        "Values after the prefix must be zero."
+    );
+    let m = divisors()
+        .iter()
+        .map_while(|&d| usize::try_from(d).ok())
+        .find(|&d| d >= nonzero_prefix && n.is_multiple_of(d))
+        .unwrap_or(n);
+    if m == n {
+        ntt(values);
+        return;
+    }
+    let root =
+        Field::root(n as u64).expect("Vector length does not divide multiplicative group order.");
+
+    // Row `c` of an `r × m` matrix gets the input twisted by `ω_N^(j·c)`.
+    let input = values[..nonzero_prefix].to_vec();
+    let mut step = Field::from(1);
+    for row in values.chunks_exact_mut(m) {
+        let mut twiddle = Field::from(1);
+        for (y, &x) in row.iter_mut().zip(&input) {
+            *y = x * twiddle;
+            twiddle *= step;
+        }
+        row[input.len()..].fill(Field::from(0));
+        step *= root;
+    }
+    strategy(m).ntt(values);
+    transpose(values, (n / m, m));
+}
+
+/// Forward NTT computing only the first `outputs` values.
+///
+/// On return `values[..outputs]` holds `X_0, ..., X_(outputs-1)` of the full
+/// transform, the rest of the buffer is unspecified.
+///
+/// With `N = m · r` and `r >= outputs`, writing the input index as
+/// `j = a · m + b` gives, for `i < r`,
+///
+/// ```text
+/// X_i = Σ_(b < m) ω_N^(b·i) Σ_(a < r) x_(a·m + b) ω_r^(a·i)
+/// ```
+///
+/// so the transform is `m` length `r` NTTs followed by `outputs` dot
+/// products of length `m`. This picks the smallest such `r`, for a cost of
+/// about `N · log(r) + N · outputs / r` instead of `N · log N`.
+///
+/// # Panics
+///
+/// Panics if `outputs` exceeds the length or the length does not divide the
+/// multiplicative group order.
+pub fn ntt_truncated(values: &mut [Field], outputs: usize) {
+    let n = values.len();
+    assert!(outputs <= n);
+    if outputs == 0 {
+        return;
+    }
+    let r = divisors()
+        .iter()
+        .map_while(|&d| usize::try_from(d).ok())
+        .find(|&d| d >= outputs && n.is_multiple_of(d))
+        .unwrap_or(n);
+    if r == n {
+        ntt(values);
+        return;
+    }
+    let m = n / r;
+    let root =
+        Field::root(n as u64).expect("Vector length does not divide multiplicative group order.");
+
+    // Row `b` of the transposed `m × r` matrix holds `x_(a·m + b)` for `a < r`.
+    transpose(values, (r, m));
+    ntt_batch(values, r);
+
+    let mut result = vec![Field::from(0); outputs];
+    let mut step = Field::from(1);
+    for row in values.chunks_exact(r) {
+        let mut twiddle = Field::from(1);
+        for (x, &y) in result.iter_mut().zip(row) {
+            *x += y * twiddle;
+            twiddle *= step;
+        }
+        step *= root;
+    }
+    values[..outputs].copy_from_slice(&result);
+}
+
+/// Complete the spectrum of a symmetric input from its first `half_len`
+/// values.
+///
+/// For an input with `x_j = x_(N-j)` (indices modulo `N`), the analogue of a
+/// real input, the spectrum is symmetric too: `X_(N-k) = X_k`. This fills
+/// `spectrum[half_len..]` using that, so only `X_0, ..., X_(N/2)` need to be
+/// computed, for example by a truncated evaluation. The precondition is not
+/// checked, for other inputs the result is wrong.
+///
+/// # Panics
+///
+/// Panics if `half_len` is less than `N / 2 + 1` or more than `N`.
+pub fn hermitian_fill(spectrum: &mut [Field], half_len: usize) {
+    let n = spectrum.len();
+    if n == 0 {
+        return;
+    }
+    assert!(half_len > n / 2 && half_len <= n);
+    for k in half_len..n {
+        spectrum[k] = spectrum[n - k];
+    }
+}
+
+/// Whether `evals`, the evaluations `p(ω^i)` as computed by [`ntt`], are of a
+/// polynomial of degree at most `max_degree`.
+///
+/// Inverse transforms a copy and checks that the coefficients above
+/// `max_degree` are zero. This is meant for debug assertions.
+///
+/// # Panics
+///
+/// Panics if the length does not divide the multiplicative group order.
+#[must_use]
+pub fn is_low_degree(evals: &[Field], max_degree: usize) -> bool {
+    let mut coefficients = evals.to_vec();
+    intt(&mut coefficients);
+    coefficients
+        .iter()
+        .skip(max_degree.saturating_add(1))
+        .all(|&c| c == Field::from(0))
+}
+
 pub fn intt(values: &mut [Field]) {
     if values.len() <= 1 {
         return;
@@ -112,13 +756,107 @@ pub fn intt(values: &mut [Field]) {
         *x *= scale;
     }
 
-    // Permute j = N - i mod N
+    strategy(values.len()).intt(values);
+}
+
+/// The previous [`intt`], reversing the input and running the forward NTT.
+#[cfg(any(test, feature = "bench"))]
+fn intt_reverse(values: &mut [Field]) {
+    if values.len() <= 1 {
+        return;
+    }
+    let scale = Field::from(values.len() as u64).inv();
+    for x in values.iter_mut() {
+        *x *= scale;
+    }
     values[1..].reverse();
+    ntt(values);
+}
+
+/// Evaluations on the subgroup of order `new_size` of the polynomial with
+/// evaluations `evals` on the subgroup of order `evals.len()`.
+///
+/// Inverse transforms to coefficients, zero pads or truncates them to
+/// `new_size` and transforms back. Truncation is only exact if the degree is
+/// less than `new_size`, otherwise the high coefficients are dropped.
+///
+/// # Panics
+///
+/// Panics if either size does not divide the multiplicative group order.
+#[must_use]
+pub fn resample(evals: &[Field], new_size: usize) -> Vec<Field> {
+    let mut values = evals.to_vec();
+    intt(&mut values);
+    values.resize(new_size, Field::from(0));
+    ntt(&mut values);
+    values
+}
 
-    // Apply forward NTT
+/// Evaluations of the polynomial with coefficients `values` on the coset
+/// `shift · ⟨ω⟩`, i.e. `c_i ← c_i · shift^i` followed by the forward NTT.
+///
+/// # Panics
+///
+/// Panics if `shift` is zero or the length does not divide the
+/// multiplicative group order.
+pub fn ntt_coset(values: &mut [Field], shift: Field) {
+    assert!(shift != Field::from(0), "Coset shift must be non-zero.");
+    let mut power = Field::from(1);
+    for value in values.iter_mut() {
+        *value *= power;
+        power *= shift;
+    }
     ntt(values);
 }
 
+/// Inverse of [`ntt_coset`], the same as [`coset_ifft_fused`].
+///
+/// # Panics
+///
+/// Panics if `shift` is zero or the length does not divide the
+/// multiplicative group order.
+pub fn intt_coset(values: &mut [Field], shift: Field) {
+    coset_ifft_fused(values, shift);
+}
+
+/// Coefficients of the polynomial with evaluations `evals` on the coset
+/// `shift · ⟨ω⟩`, i.e. the inverse NTT followed by `c_i ← c_i · shift^(-i)`.
+///
+/// Uses `intt(x)_i = ntt(x)_(N-i) / N`: after the forward NTT a single pass
+/// does the index reversal, the `1 / N` scale and the untwist together. The
+/// composition of [`intt`] and an untwist takes three passes for these.
+///
+/// # Panics
+///
+/// Panics if `shift` is zero or the length does not divide the
+/// multiplicative group order.
+pub fn coset_ifft_fused(evals: &mut [Field], shift: Field) {
+    let n = evals.len();
+    if n == 0 {
+        return;
+    }
+    assert!(shift != Field::from(0), "Coset shift must be non-zero.");
+    ntt(evals);
+    let shift_inv = shift.inv();
+    let scale = Field::from(n as u64).inv();
+    evals[0] *= scale;
+
+    // Walk inwards from both ends, with `low = shift^(-i) / N` and
+    // `high = shift^(-j) / N` for `j = N - i`.
+    let mut low = scale * shift_inv;
+    let mut high = scale * shift_inv.pow(n as u64 - 1);
+    let (head, tail) = evals[1..].split_at_mut((n - 1) / 2);
+    for (a, b) in head.iter_mut().zip(tail.iter_mut().rev()) {
+        (*a, *b) = (*b * low, *a * high);
+        low *= shift_inv;
+        high *= shift;
+    }
+    if n.is_multiple_of(2) {
+        // The middle element is its own reverse.
+        tail[0] *= low;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,7 +866,8 @@ mod tests {
     #[track_caller]
     pub fn test_ntt(ntt: impl Ntt) {
         let mut rng = StdRng::seed_from_u64(Field::MODULUS);
-        let mut values = (0..ntt.len()).map(|_| rng.gen()).collect::<Vec<_>>();
+        let input = (0..ntt.len()).map(|_| rng.gen()).collect::<Vec<_>>();
+        let mut values = input.clone();
         let mut expected = values.clone();
         naive::ntt(&mut expected);
         ntt.ntt(&mut values);
@@ -136,6 +875,452 @@ mod tests {
         for (&value, expected) in values.iter().zip(expected) {
             assert_eq!(value, expected);
         }
+        ntt.intt(&mut values);
+        let size = Field::from(ntt.len() as u64);
+        assert!(values.iter().zip(&input).all(|(&x, &y)| x == y * size));
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, feature = "paranoid"))]
+    fn test_ntt_canonical() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let mut values = (0..63).map(|_| rng.gen()).collect::<Vec<Field>>();
+        values.push(Field::from(Field::MODULUS - 1));
+        let mut expected = values.clone();
+        naive::ntt(&mut expected);
+        ntt(&mut values);
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, feature = "paranoid"))]
+    #[should_panic(expected = "NTT input 3 is not canonical")]
+    fn test_ntt_non_canonical() {
+        let mut values = vec![Field::from(1); 8];
+        values[3] = bytemuck::cast(Field::MODULUS);
+        ntt(&mut values);
+    }
+
+    #[test]
+    fn test_ntt_into_uninit() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 8, 240, 257] {
+            let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            ntt(&mut expected);
+
+            let mut output = Vec::with_capacity(size);
+            let result = ntt_into_uninit(&input, &mut output.spare_capacity_mut()[..size]);
+            assert_eq!(result, expected);
+            // Safety: `ntt_into_uninit` initialized the first `size` elements.
+            unsafe { output.set_len(size) };
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn test_ntt_from_iter() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 8, 240, 257] {
+            let terms = (0..=size / 4)
+                .map(|_| (rng.gen_range(0..size), rng.gen()))
+                .collect::<Vec<(usize, Field)>>();
+            let mut expected = vec![Field::from(0); size];
+            for &(index, coeff) in &terms {
+                expected[index] += coeff;
+            }
+            ntt(&mut expected);
+            assert_eq!(ntt_from_iter(terms.into_iter(), size), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_ntt_from_iter_out_of_range() {
+        let _ = ntt_from_iter([(8, Field::from(1))].into_iter(), 8);
+    }
+
+    #[test]
+    fn test_reference_vector() {
+        let (input, output) = reference_vector(0, 16);
+        // First SplitMix64 output for seed 0.
+        assert_eq!(input[0], Field::from(0xe220_a839_7b1d_cdaf_u64));
+        assert_eq!(output[0], input.iter().copied().sum());
+        assert_eq!(output[0], Field::from(6_498_531_506_807_609_934_u64));
+        assert_eq!(reference_vector(0, 16), (input.clone(), output.clone()));
+        let mut values = input;
+        ntt(&mut values);
+        assert_eq!(values, output);
+        assert_ne!(reference_vector(1, 16).0, reference_vector(0, 16).0);
+    }
+
+    #[test]
+    fn test_coset_ifft_fused() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 3, 8, 12, 240, 257] {
+            let evals = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let shift: Field = rng.gen();
+            let mut expected = evals.clone();
+            intt(&mut expected);
+            let shift_inv = shift.inv();
+            let mut power = Field::from(1);
+            for c in &mut expected {
+                *c *= power;
+                power *= shift_inv;
+            }
+            let mut values = evals;
+            coset_ifft_fused(&mut values, shift);
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    fn test_resample() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let coefficients = (0..11).map(|_| rng.gen()).collect::<Vec<Field>>();
+        let mut evals = coefficients.clone();
+        evals.resize(16, Field::from(0));
+        ntt(&mut evals);
+
+        let up = resample(&evals, 240);
+        let root = Field::root(240).unwrap();
+        for (i, &value) in up.iter().enumerate() {
+            assert_eq!(value, crate::poly::eval(&coefficients, root.pow(i as u64)));
+        }
+        assert_eq!(resample(&up, 16), evals);
+        assert_eq!(resample(&up, 12), resample(&evals, 12));
+    }
+
+    #[test]
+    fn test_ntt_coset() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let shift = Field::from(7);
+        for size in [1_usize, 2, 12, 256] {
+            let coefficients = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut values = coefficients.clone();
+            ntt_coset(&mut values, shift);
+            let root = Field::root(size as u64).unwrap();
+            for (i, &value) in values.iter().enumerate() {
+                let x = shift * root.pow(i as u64);
+                assert_eq!(value, crate::poly::eval(&coefficients, x));
+            }
+            intt_coset(&mut values, shift);
+            assert_eq!(values, coefficients);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Coset shift must be non-zero.")]
+    fn test_ntt_coset_zero_shift() {
+        ntt_coset(&mut [Field::from(1); 4], Field::from(0));
+    }
+
+    #[test]
+    fn test_ntt_truncated() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (size, outputs) in [(1, 0), (1, 1), (16, 3), (240, 17), (256, 256), (1024, 5)] {
+            let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            naive::ntt(&mut expected);
+            let mut values = input;
+            ntt_truncated(&mut values, outputs);
+            assert_eq!(values[..outputs], expected[..outputs]);
+        }
+    }
+
+    #[test]
+    fn test_ntt_in_pool() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 240, 1 << 10] {
+            let mut values = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = values.clone();
+            naive::ntt(&mut expected);
+            ntt_in_pool(&mut values, &pool);
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    fn test_ntt_batch() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (size, rows) in [(1, 3), (8, 5), (240, 7), (256, 100), (1 << 11, 3)] {
+            let input = (0..size * rows).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            for chunk in expected.chunks_exact_mut(size) {
+                naive::ntt(chunk);
+            }
+            let mut values = input.clone();
+            ntt_batch(&mut values, size);
+            assert_eq!(values, expected);
+            intt_batch(&mut values, size);
+            assert_eq!(values, input);
+        }
+    }
+
+    #[test]
+    fn test_ntt2d() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (rows, cols) in [(1, 1), (1, 8), (4, 1), (4, 4), (3, 8), (16, 5), (12, 10)] {
+            let input = (0..rows * cols).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let root_rows = Field::root(rows as u64).unwrap();
+            let root_cols = Field::root(cols as u64).unwrap();
+            let mut expected = vec![Field::from(0); rows * cols];
+            for k in 0..rows {
+                for l in 0..cols {
+                    for i in 0..rows {
+                        for j in 0..cols {
+                            expected[k * cols + l] += input[i * cols + j]
+                                * root_rows.pow((i * k) as u64)
+                                * root_cols.pow((j * l) as u64);
+                        }
+                    }
+                }
+            }
+            let mut values = input.clone();
+            ntt2d(&mut values, (rows, cols));
+            assert_eq!(values, expected, "{rows} × {cols}");
+            intt2d(&mut values, (rows, cols));
+            assert_eq!(values, input);
+        }
+    }
+
+    #[test]
+    fn test_ntt_columns() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (rows, cols) in [(1, 1), (8, 1), (4, 3), (16, 5), (240, 7), (12, 100)] {
+            let input = (0..rows * cols).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            for col in 0..cols {
+                let mut column = (0..rows)
+                    .map(|row| input[row * cols + col])
+                    .collect::<Vec<_>>();
+                naive::ntt(&mut column);
+                for (row, value) in column.into_iter().enumerate() {
+                    expected[row * cols + col] = value;
+                }
+            }
+            let mut values = input;
+            ntt_columns(&mut values, (rows, cols));
+            assert_eq!(values, expected, "{rows} × {cols}");
+        }
+    }
+
+    #[test]
+    fn test_intt() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 12, 240, 256, 257, 1 << 12, 1 << 16, 3 << 14, 1 << 18] {
+            let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            intt_reverse(&mut expected);
+            let mut values = input.clone();
+            intt(&mut values);
+            assert_eq!(values, expected, "size {size}");
+            ntt(&mut values);
+            assert_eq!(values, input);
+        }
+    }
+
+    #[test]
+    fn test_time_shift() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (size, k) in [(1, 0), (8, 3), (12, 12), (240, 17), (257, 1000)] {
+            let values = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = values.clone();
+            expected.rotate_right(k % size);
+            let mut spectrum = values;
+            ntt(&mut spectrum);
+            time_shift(&mut spectrum, k);
+            intt(&mut spectrum);
+            assert_eq!(spectrum, expected);
+        }
+    }
+
+    #[test]
+    fn test_subgroup_power_sum() {
+        assert_eq!(subgroup_power_sum(16, 0), Field::from(16));
+        assert_eq!(subgroup_power_sum(16, 1), Field::from(0));
+        assert_eq!(subgroup_power_sum(16, 16), Field::from(16));
+        for n in [1_usize, 2, 12, 17] {
+            let root = Field::root(n as u64).unwrap();
+            for k in 0..3 * n {
+                let expected = (0..n).map(|i| root.pow((i * k) as u64)).sum::<Field>();
+                assert_eq!(subgroup_power_sum(n, k), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ntt_maybe_special() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 12, 64, 257] {
+            let constant = vec![rng.gen(); size];
+            let mut delta = vec![Field::from(0); size];
+            delta[size / 3] = rng.gen();
+            let random = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            for input in [constant, delta, random] {
+                let mut values = input.clone();
+                let mut expected = input;
+                naive::ntt(&mut expected);
+                ntt_maybe_special(&mut values);
+                assert_eq!(values, expected);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, feature = "paranoid"))]
+    #[should_panic(expected = "NTT input 0 is not canonical")]
+    fn test_ntt_maybe_special_non_canonical() {
+        // A constant input, which takes the first fast path.
+        ntt_maybe_special(&mut [bytemuck::cast(Field::MODULUS); 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "7 is not a supported NTT size")]
+    fn test_ntt_maybe_special_unsupported() {
+        // Panics for constant inputs too, not only where the fast paths end.
+        ntt_maybe_special(&mut [Field::from(3); 7]);
+    }
+
+    #[test]
+    fn test_ntt_pruned() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (size, prefix) in [
+            (1, 1),
+            (8, 0),
+            (8, 1),
+            (8, 3),
+            (240, 7),
+            (240, 240),
+            (1024, 100),
+        ] {
+            let mut values = vec![Field::from(0); size];
+            for value in &mut values[..prefix] {
+                *value = rng.gen();
+            }
+            let mut expected = values.clone();
+            naive::ntt(&mut expected);
+            ntt_pruned(&mut values, prefix);
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    fn test_hermitian_fill() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1_usize, 2, 3, 8, 12, 240, 257] {
+            let mut values = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            for j in 1..size {
+                values[size - j] = values[j];
+            }
+            naive::ntt(&mut values);
+            let mut spectrum = values.clone();
+            spectrum[size / 2 + 1..].fill(Field::from(0));
+            hermitian_fill(&mut spectrum, size / 2 + 1);
+            assert_eq!(spectrum, values);
+        }
+    }
+
+    #[test]
+    fn test_is_low_degree() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let mut values = vec![Field::from(0); 240];
+        for value in &mut values[..=17] {
+            *value = rng.gen();
+        }
+        ntt(&mut values);
+        assert!(is_low_degree(&values, 17));
+        assert!(is_low_degree(&values, 239));
+        assert!(!is_low_degree(&values, 16));
+
+        let random = (0..240).map(|_| rng.gen()).collect::<Vec<Field>>();
+        assert!(!is_low_degree(&random, 200));
+        assert!(is_low_degree(&random, usize::MAX));
+    }
+
+    #[test]
+    fn test_plan_objectives() {
+        for size in [240, 1020, 1024, 1 << 12, 3 << 10] {
+            test_ntt(plan(size, PlanObjective::MinOps));
+            test_ntt(plan(size, PlanObjective::MinPasses));
+        }
+    }
+
+    #[test]
+    fn test_plan_split() {
+        assert_eq!(plan_split(128, PlanObjective::MinPasses), None);
+        assert_eq!(plan_split(257, PlanObjective::MinOps), None);
+        // The coprime 20 × 51 over the more balanced 30 × 34.
+        assert_eq!(plan_split(1020, PlanObjective::MinOps), Some((20, 51)));
+        assert_eq!(plan_split(1020, PlanObjective::MinPasses), Some((30, 34)));
+        // The objectives differ for large sizes too.
+        assert_eq!(
+            plan_split(1_048_560, PlanObjective::MinOps),
+            Some((816, 1285))
+        );
+        assert_eq!(
+            plan_split(1_048_560, PlanObjective::MinPasses),
+            Some((257, 4080))
+        );
+        assert_eq!(plan_split(1 << 20, PlanObjective::MinPasses), None);
+        assert_eq!(
+            plan_split(1 << 24, PlanObjective::MinOps),
+            Some((1 << 12, 1 << 12))
+        );
+        assert_eq!(
+            plan_split(1 << 24, PlanObjective::MinPasses),
+            Some((1 << 12, 1 << 12))
+        );
+    }
+
+    #[test]
+    fn test_best_factorization() {
+        assert_eq!(best_factorization(1), vec![1]);
+        assert_eq!(best_factorization(15), vec![15]);
+        assert_eq!(best_factorization(255), vec![15, 17]);
+        assert_eq!(best_factorization(1020), vec![20, 51]);
+        assert_eq!(best_factorization(1 << 10), vec![32, 32]);
+        assert_eq!(best_factorization(1 << 20), vec![1 << 20]);
+        assert_eq!(best_factorization(1 << 24), vec![1 << 12, 1 << 12]);
+    }
+
+    #[test]
+    fn test_radix_4_dispatch() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1 << 11, 1 << 12] {
+            let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            naive::ntt(&mut expected);
+            let mut values = input.clone();
+            ntt(&mut values);
+            assert_eq!(values, expected, "size {size}");
+            intt(&mut values);
+            assert_eq!(values, input);
+        }
+    }
+
+    #[test]
+    fn test_prime_dispatch() {
+        test_ntt(strategy(257));
+        assert_eq!(plan_split(257, PlanObjective::MinPasses), None);
+        // Primes not dividing the group order have no NTT at all.
+        for size in [7, 11, 13] {
+            assert_eq!(NttPlan::new(size).err(), Some(UnsupportedSize(size)));
+        }
+    }
+
+    #[test]
+    fn test_good_thomas_dispatch() {
+        // Coprime splits, `35 = 5 · 7` does not divide the group order.
+        for size in [255, 1020, 3 * 257, 15 * 257] {
+            let (a, b) = plan_split(size, PlanObjective::default()).unwrap();
+            assert_eq!(gcd(a, b), 1);
+            test_ntt(strategy(size));
+        }
     }
 }
 
@@ -150,13 +1335,74 @@ pub mod bench {
 
     pub fn group(criterion: &mut Criterion) {
         rader::bench::group(criterion);
+        batch::bench::group(criterion);
         small::bench::group(criterion);
+        bluestein::bench::group(criterion);
+        cooley_tukey::bench::group(criterion);
+        cooley_tukey_dif::bench::group(criterion);
         good_thomas::bench::group(criterion);
+        stockham::bench::group(criterion);
+        split_radix::bench::group(criterion);
+        six_step::bench::group(criterion);
+        bench_intt(criterion, 1 << 16);
+        bench_intt(criterion, 1 << 20);
+        // Not a radix-4 leaf, goes through `CooleyTukey::transform`.
+        bench_intt(criterion, 1 << 24);
+        bench_coset_ifft(criterion, 1 << 22);
+        bench_truncated(criterion, 1 << 20, 16);
 
         #[cfg(feature = "gpu")]
         gpu::bench::group(criterion);
     }
 
+    fn bench_intt(criterion: &mut Criterion, size: usize) {
+        let mut rng = thread_rng();
+        let mut values = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+        let mut group = criterion.benchmark_group("ntt/intt");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("reverse", size), |bencher| {
+            bencher.iter(|| intt_reverse(&mut values));
+        });
+        group.bench_function(BenchmarkId::new("direct", size), |bencher| {
+            bencher.iter(|| intt(&mut values));
+        });
+    }
+
+    fn bench_coset_ifft(criterion: &mut Criterion, size: usize) {
+        let mut rng = thread_rng();
+        let mut values = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+        let shift: Field = rng.gen();
+        let mut group = criterion.benchmark_group("ntt/coset_ifft");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("composed", size), |bencher| {
+            bencher.iter(|| {
+                intt(&mut values);
+                let shift_inv = shift.inv();
+                let mut power = Field::from(1);
+                for c in &mut values {
+                    *c *= power;
+                    power *= shift_inv;
+                }
+            });
+        });
+        group.bench_function(BenchmarkId::new("fused", size), |bencher| {
+            bencher.iter(|| coset_ifft_fused(&mut values, shift));
+        });
+    }
+
+    fn bench_truncated(criterion: &mut Criterion, size: usize, outputs: usize) {
+        let mut rng = thread_rng();
+        let mut values = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+        let mut group = criterion.benchmark_group("ntt/truncated");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("full", size), |bencher| {
+            bencher.iter(|| ntt(&mut values));
+        });
+        group.bench_function(BenchmarkId::new(format!("{outputs}"), size), |bencher| {
+            bencher.iter(|| ntt_truncated(&mut values, outputs));
+        });
+    }
+
     pub fn bench_ntt(criterion: &mut Criterion, name: &str, ntt: impl Ntt) {
         // Use a multiple of the NTT size larger than the minimum work size.
         let size = ntt.len();
@@ -0,0 +1,162 @@
+//! Negacyclic transforms, for polynomials modulo `x^n + 1`.
+
+use super::{strategy, Ntt};
+use crate::Field;
+
+/// Negacyclic forward NTT: `c_i ← c_i · ψ^i` for a primitive `2n`-th root `ψ`
+/// followed by the cyclic NTT.
+///
+/// The pointwise product of two transforms is the transform of the product
+/// modulo `x^n + 1`, see [`negacyclic_mul`].
+///
+/// # Panics
+///
+/// Panics if `2n` does not divide the multiplicative group order.
+pub fn ntt(values: &mut [Field]) {
+    if values.is_empty() {
+        return;
+    }
+    forward(values, &twiddles(values.len()));
+}
+
+/// Inverse of the negacyclic [`ntt`].
+///
+/// # Panics
+///
+/// Panics if `2n` does not divide the multiplicative group order.
+pub fn intt(values: &mut [Field]) {
+    if values.is_empty() {
+        return;
+    }
+    inverse(values, &twiddles(values.len()));
+}
+
+/// Multiply two polynomials modulo `x^n + 1`.
+///
+/// Coefficients are given from low to high degree. Computed by twisting both
+/// operands with powers of a primitive `2n`-th root `ψ`, a cyclic NTT,
+/// pointwise product and inverse NTT. The twists share one table of powers,
+/// using `ψ^(-i) = -ψ^(n-i)` for the inverse twist.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths, or if `2n` does not divide the
+/// multiplicative group order. In particular every power of two up to `2^31` is
+/// supported.
+#[must_use]
+pub fn negacyclic_mul(a: &[Field], b: &[Field]) -> Vec<Field> {
+    assert_eq!(a.len(), b.len());
+    let n = a.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let twiddles = twiddles(n);
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    forward(&mut a, &twiddles);
+    forward(&mut b, &twiddles);
+    for (a, b) in a.iter_mut().zip(&b) {
+        *a *= *b;
+    }
+    inverse(&mut a, &twiddles);
+    a
+}
+
+/// The powers `ψ^i` for `i < n`.
+fn twiddles(n: usize) -> Vec<Field> {
+    let psi = Field::root(2 * n as u64)
+        .expect("Twice the length must divide the multiplicative group order.");
+    let mut twiddles = Vec::with_capacity(n);
+    let mut psi_i = Field::from(1);
+    for _ in 0..n {
+        twiddles.push(psi_i);
+        psi_i *= psi;
+    }
+    twiddles
+}
+
+fn forward(values: &mut [Field], twiddles: &[Field]) {
+    for (value, &t) in values.iter_mut().zip(twiddles) {
+        *value *= t;
+    }
+    strategy(values.len()).ntt(values);
+}
+
+fn inverse(values: &mut [Field], twiddles: &[Field]) {
+    // Inverse NTT as a reversed forward NTT, with the 1/n scaling folded into
+    // the inverse twist.
+    values[1..].reverse();
+    strategy(values.len()).ntt(values);
+    let scale = Field::from(values.len() as u64).inv();
+    values[0] *= scale;
+    let scale = -scale;
+    for (value, &t) in values[1..].iter_mut().zip(twiddles[1..].iter().rev()) {
+        *value *= scale * t;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn negacyclic_naive(a: &[Field], b: &[Field]) -> Vec<Field> {
+        let n = a.len();
+        let mut result = vec![Field::from(0); n];
+        for i in 0..n {
+            for j in 0..n {
+                if i + j < n {
+                    result[i + j] += a[i] * b[j];
+                } else {
+                    result[i + j - n] -= a[i] * b[j];
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_negacyclic_mul() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [0, 1, 2, 4, 8, 64, 256, 1024, 3, 12] {
+            let a = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let b = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            assert_eq!(negacyclic_mul(&a, &b), negacyclic_naive(&a, &b));
+        }
+    }
+
+    #[test]
+    fn test_negacyclic_wrap() {
+        // x^(n-1) * x = x^n = -1
+        let mut a = vec![Field::from(0); 8];
+        let mut b = a.clone();
+        a[7] = Field::from(1);
+        b[1] = Field::from(1);
+        let mut expected = vec![Field::from(0); 8];
+        expected[0] = -Field::from(1);
+        assert_eq!(negacyclic_mul(&a, &b), expected);
+    }
+
+    #[test]
+    fn test_negacyclic_ntt() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [4, 8, 16] {
+            let a = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let b = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut a_hat = a.clone();
+            let mut b_hat = b.clone();
+            ntt(&mut a_hat);
+            ntt(&mut b_hat);
+            let mut product = a_hat
+                .iter()
+                .zip(&b_hat)
+                .map(|(&a, &b)| a * b)
+                .collect::<Vec<_>>();
+            intt(&mut product);
+            assert_eq!(product, negacyclic_naive(&a, &b));
+
+            intt(&mut a_hat);
+            assert_eq!(a_hat, a);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Reusable transform plans.
+
+use super::{strategy, Ntt};
+use crate::{divisors::is_divisor, Field};
+use std::{fmt, sync::Arc};
+
+/// Error creating an [`NttPlan`] for a size that does not divide the
+/// multiplicative group order.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct UnsupportedSize(pub usize);
+
+impl fmt::Display for UnsupportedSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is not a supported NTT size (does not divide multiplicative order)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedSize {}
+
+/// Forward and inverse NTTs of a fixed size.
+///
+/// Holds the decomposition picked by the planner, with its twiddle tables,
+/// and the inverse scaling factor, so repeated transforms of the same size
+/// skip planning and the cache lookup.
+#[derive(Clone)]
+pub struct NttPlan {
+    ntt:   Arc<dyn Ntt>,
+    scale: Field,
+}
+
+impl NttPlan {
+    /// # Errors
+    ///
+    /// Returns [`UnsupportedSize`] if `size` does not divide the
+    /// multiplicative group order.
+    pub fn new(size: usize) -> Result<Self, UnsupportedSize> {
+        if !is_divisor(size) {
+            return Err(UnsupportedSize(size));
+        }
+        Ok(Self {
+            ntt:   strategy(size),
+            scale: Field::from(size as u64).inv(),
+        })
+    }
+
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.ntt.len()
+    }
+
+    /// Forward NTT, the same as [`super::ntt`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` does not have the planned length.
+    pub fn forward(&self, values: &mut [Field]) {
+        assert_eq!(values.len(), self.size());
+        self.ntt.ntt(values);
+    }
+
+    /// Inverse NTT, the same as [`super::intt`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` does not have the planned length.
+    pub fn inverse(&self, values: &mut [Field]) {
+        assert_eq!(values.len(), self.size());
+        for x in values.iter_mut() {
+            *x *= self.scale;
+        }
+        self.ntt.intt(values);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::naive, *};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_plan() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 3, 12, 17, 256, 1020] {
+            let plan = NttPlan::new(size).unwrap();
+            assert_eq!(plan.size(), size);
+            for _ in 0..2 {
+                let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+                let mut expected = input.clone();
+                naive::ntt(&mut expected);
+                let mut values = input.clone();
+                plan.forward(&mut values);
+                assert_eq!(values, expected);
+                plan.inverse(&mut values);
+                assert_eq!(values, input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert_eq!(NttPlan::new(7).err(), Some(UnsupportedSize(7)));
+        assert_eq!(NttPlan::new(1000).err(), Some(UnsupportedSize(1000)));
+    }
+}
@@ -7,6 +7,12 @@ use crate::{
 use rayon::prelude::*;
 use std::sync::Arc;
 
+/// The primes dividing the multiplicative group order. Apart from 2 these are
+/// Fermat primes, so the cyclic convolution of size `p - 1` is a power of two
+/// and never needs Bluestein.
+pub const PRIMES: [usize; 6] = [2, 3, 5, 17, 257, 65537];
+
+/// Rader's algorithm for the sizes in [`PRIMES`].
 pub struct Rader {
     size:      usize,
     permute_i: Arc<dyn Permute<Field>>,
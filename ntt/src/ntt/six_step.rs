@@ -0,0 +1,125 @@
+//! Six-step NTT for sizes `n = n1 · n2`.
+//!
+//! The input is read as a row-major `n1 × n2` matrix and transformed in six
+//! passes, each of which streams over the data once:
+//!
+//! 1. Transpose to `n2 × n1`.
+//! 2. Transform the `n2` rows of length `n1`.
+//! 3. Multiply entry `(j2, k1)` by the twiddle `ω^(j2 · k1)`.
+//! 4. Transpose to `n1 × n2`.
+//! 5. Transform the `n1` rows of length `n2`.
+//! 6. Transpose to `n2 × n1`, giving `X[k1 + n1 · k2]` in natural order.
+//!
+//! The inner transforms only touch one row at a time, so for `n1, n2 ≈ √n`
+//! they stay in cache even when the whole vector does not. Unlike
+//! [`CooleyTukey`](super::cooley_tukey::CooleyTukey) the twiddles are never
+//! tabulated, only one power of `ω` per row is computed.
+
+use super::{Ntt, MIN_WORK_SIZE};
+use crate::{permute::transpose, utils::div_round_up, Field};
+use rayon::prelude::*;
+use std::{cmp, sync::Arc};
+
+pub struct SixStep {
+    split:        (usize, usize),
+    inner_1:      Arc<dyn Ntt>,
+    inner_2:      Arc<dyn Ntt>,
+    root:         Field,
+    n1_work_size: usize,
+    n2_work_size: usize,
+}
+
+impl SixStep {
+    /// Six-step transform of size `n1 · n2` with planned inner transforms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n1 · n2` does not divide the multiplicative group order.
+    #[must_use]
+    pub fn new(n1: usize, n2: usize) -> Self {
+        let size = n1 * n2;
+        let root = Field::root(size as u64)
+            .expect("Vector length does not divide multiplicative group order.");
+        Self {
+            split: (n1, n2),
+            inner_1: super::strategy(n1),
+            inner_2: super::strategy(n2),
+            root,
+            n1_work_size: cmp::min(size, div_round_up(MIN_WORK_SIZE, n1) * n1),
+            n2_work_size: cmp::min(size, div_round_up(MIN_WORK_SIZE, n2) * n2),
+        }
+    }
+}
+
+impl Ntt for SixStep {
+    fn len(&self) -> usize {
+        self.split.0 * self.split.1
+    }
+
+    fn ntt(&self, values: &mut [Field]) {
+        let (n1, n2) = self.split;
+        debug_assert_eq!(values.len() % self.len(), 0);
+        for values in values.chunks_exact_mut(self.len()) {
+            transpose(values, (n1, n2));
+            values
+                .par_chunks_mut(self.n1_work_size)
+                .enumerate()
+                .for_each(|(i, block)| {
+                    self.inner_1.ntt(block);
+                    let first_row = i * self.n1_work_size / n1;
+                    let mut omega_row = self.root.pow(first_row as u64);
+                    for row in block.chunks_exact_mut(n1) {
+                        let mut twiddle = omega_row;
+                        for value in &mut row[1..] {
+                            *value *= twiddle;
+                            twiddle *= omega_row;
+                        }
+                        omega_row *= self.root;
+                    }
+                });
+            transpose(values, (n2, n1));
+            values
+                .par_chunks_mut(self.n2_work_size)
+                .for_each(|block| self.inner_2.ntt(block));
+            transpose(values, (n1, n2));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::test_ntt, *};
+
+    #[test]
+    fn test_six_step() {
+        for (n1, n2) in [
+            (1, 1),
+            (1, 8),
+            (8, 1),
+            (2, 3),
+            (32, 32),
+            (8, 128),
+            (16, 24),
+            (5, 17),
+        ] {
+            test_ntt(SixStep::new(n1, n2));
+        }
+    }
+}
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::{
+        super::{bench::bench_ntt, cooley_tukey::CooleyTukey},
+        *,
+    };
+    use criterion::Criterion;
+
+    pub fn group(criterion: &mut Criterion) {
+        for (n1, n2) in [(256, 256), (2048, 2048)] {
+            bench_ntt(criterion, "six_step", SixStep::new(n1, n2));
+            bench_ntt(criterion, "six_step/cooley_tukey", CooleyTukey::new(n1, n2));
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Split-radix NTT for power-of-two sizes.
+//!
+//! Splits `X = DFT_n(x)` into `U = DFT_{n/2}(x[2m])` and the quarter
+//! transforms `Z = DFT_{n/4}(x[4m + 1])` and `Z' = DFT_{n/4}(x[4m + 3])`.
+//! With `a = ω^k Z[k]`, `b = ω^{3k} Z'[k]` and `j = ω^{n/4}` a primitive
+//! fourth root of unity,
+//!
+//! ```text
+//! X[k]          = U[k]       + (a + b)
+//! X[k + n/2]    = U[k]       - (a + b)
+//! X[k + n/4]    = U[k + n/4] + j (a - b)
+//! X[k + 3n/4]   = U[k + n/4] - j (a - b)
+//! ```
+//!
+//! In the Goldilocks field `j = 2^48`, so multiplying by it is a shift. That
+//! leaves two multiplications per four outputs, for about `n/3 · log₂ n`
+//! in total against `n/2 · log₂ n` for radix 2.
+
+use crate::Field;
+
+/// Forward NTT of `values` in place, the same as [`super::ntt`].
+///
+/// # Panics
+///
+/// Panics if the length is not a power of two dividing the multiplicative
+/// group order.
+pub fn ntt(values: &mut [Field]) {
+    let size = values.len();
+    assert!(size.is_power_of_two(), "Length must be a power of two.");
+    if size <= 1 {
+        return;
+    }
+    let root = Field::root(size as u64)
+        .expect("Vector length does not divide multiplicative group order.");
+
+    // `ω^i` for `i < 3n/4`, covering the `ω^{3k}` twiddles of the top level.
+    let twiddles = (0..3 * size / 4)
+        .scan(Field::from(1), |omega, _| {
+            let result = *omega;
+            *omega *= root;
+            Some(result)
+        })
+        .collect::<Vec<_>>();
+    let input = values.to_vec();
+    recurse(&input, 1, values, &twiddles, 1);
+}
+
+/// The fourth root of unity `j = ω_n^{n/4} = Field::root(4)` is `2^48`.
+const J_SHIFT: u64 = 48;
+
+/// Transform `input[i · stride]` for `i < output.len()` into `output`, with
+/// `ω^k = twiddles[k · twiddle_stride]`.
+fn recurse(
+    input: &[Field],
+    stride: usize,
+    output: &mut [Field],
+    twiddles: &[Field],
+    twiddle_stride: usize,
+) {
+    let size = output.len();
+    match size {
+        1 => output[0] = input[0],
+        2 => {
+            let (a, b) = (input[0], input[stride]);
+            output[0] = a + b;
+            output[1] = a - b;
+        }
+        _ => {
+            let quarter = size / 4;
+            let (u, rest) = output.split_at_mut(size / 2);
+            let (z, z3) = rest.split_at_mut(quarter);
+            recurse(input, 2 * stride, u, twiddles, 2 * twiddle_stride);
+            recurse(
+                &input[stride..],
+                4 * stride,
+                z,
+                twiddles,
+                4 * twiddle_stride,
+            );
+            recurse(
+                &input[3 * stride..],
+                4 * stride,
+                z3,
+                twiddles,
+                4 * twiddle_stride,
+            );
+
+            let (u0, u1) = u.split_at_mut(quarter);
+            for k in 0..quarter {
+                let (a, b) = if k == 0 {
+                    (z[0], z3[0])
+                } else {
+                    (
+                        z[k] * twiddles[k * twiddle_stride],
+                        z3[k] * twiddles[3 * k * twiddle_stride],
+                    )
+                };
+                let sum = a + b;
+                let difference = (a - b) << J_SHIFT;
+                let (x0, x1) = (u0[k], u1[k]);
+                u0[k] = x0 + sum;
+                z[k] = x0 - sum;
+                u1[k] = x1 + difference;
+                z3[k] = x1 - difference;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::naive, *};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_j_shift() {
+        assert_eq!(Field::root(4), Some(Field::from(1) << J_SHIFT));
+    }
+
+    #[test]
+    fn test_split_radix() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048, 4096] {
+            let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            naive::ntt(&mut expected);
+            let mut values = input;
+            ntt(&mut values);
+            assert_eq!(values, expected, "size {size}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "counters")]
+    fn test_mul_count() {
+        use crate::field::algo::counters;
+        let size = 1 << 12;
+        let mut values = vec![Field::from(1); size];
+        let _ = Field::root(size as u64);
+        counters::reset();
+        ntt(&mut values);
+        let count = counters::mul_count();
+        // Radix 2 takes n/2 · log₂ n - n + 1 non-trivial multiplications. The
+        // twiddle table and root take less than another n.
+        let radix_2 = size / 2 * 12 - size + 1;
+        assert!(count < (radix_2 * 2 / 3 + size) as u64, "{count}");
+    }
+}
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::{
+        super::{cooley_tukey::CooleyTukey, Ntt},
+        *,
+    };
+    use crate::bench::rand_vec;
+    use criterion::{BenchmarkId, Criterion, Throughput};
+
+    pub fn group(criterion: &mut Criterion) {
+        bench_split_radix(criterion, 64, 64);
+        bench_split_radix(criterion, 256, 256);
+    }
+
+    fn bench_split_radix(criterion: &mut Criterion, a: usize, b: usize) {
+        let size = a * b;
+        let mut values: Vec<Field> = rand_vec(size);
+        let mut group = criterion.benchmark_group("ntt/split_radix");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("split_radix", size), |bencher| {
+            bencher.iter(|| ntt(&mut values));
+        });
+        let cooley_tukey = CooleyTukey::new(a, b);
+        group.bench_function(BenchmarkId::new("cooley_tukey", size), |bencher| {
+            bencher.iter(|| cooley_tukey.ntt(&mut values));
+        });
+    }
+}
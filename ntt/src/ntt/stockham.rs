@@ -0,0 +1,187 @@
+//! Stockham autosort NTT.
+//!
+//! Each pass reads one buffer and writes the other, such that the output ends
+//! up in natural order without a separate permutation pass. The access
+//! pattern is regular, which suits SIMD and GPUs.
+
+use crate::Field;
+
+/// Out-of-place forward NTT of `input` into `output`, the same as
+/// [`super::ntt`].
+///
+/// Uses radix 4 and 2 passes where possible, and naive passes for the odd
+/// prime factors. Sizes with the large factors 257 or 65537 are therefore
+/// slow.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths or the length does
+/// not divide the multiplicative group order.
+pub fn ntt(input: &[Field], output: &mut [Field]) {
+    assert_eq!(input.len(), output.len());
+    let size = input.len();
+    if size <= 1 {
+        output.copy_from_slice(input);
+        return;
+    }
+    let mut root = Field::root(size as u64)
+        .expect("Vector length does not divide multiplicative group order.");
+    let radices = radices(size);
+    let mut scratch = if radices.len() > 1 {
+        vec![Field::from(0); size]
+    } else {
+        Vec::new()
+    };
+
+    let mut len = size;
+    let mut stride = 1;
+    for (i, &radix) in radices.iter().enumerate() {
+        // Alternate buffers such that the last pass writes `output`.
+        let to_output = (radices.len() - i) % 2 == 1;
+        let (from, to): (&[Field], &mut [Field]) = match (i, to_output) {
+            (0, true) => (input, output),
+            (0, false) => (input, &mut scratch),
+            (_, true) => (&scratch, output),
+            (_, false) => (output, &mut scratch),
+        };
+        pass(from, to, radix, len, stride, root);
+        len /= radix;
+        stride *= radix;
+        root = root.pow(radix as u64);
+    }
+}
+
+/// Factor `size` into the radices of the passes.
+fn radices(mut size: usize) -> Vec<usize> {
+    let mut result = Vec::new();
+    for radix in [4, 2, 3, 5, 17, 257, 65537] {
+        while size.is_multiple_of(radix) {
+            result.push(radix);
+            size /= radix;
+        }
+    }
+    assert_eq!(
+        size, 1,
+        "Vector length does not divide multiplicative group order."
+    );
+    result
+}
+
+/// One decimation-in-frequency pass on `stride` interleaved transforms of
+/// length `len` with primitive root `root`.
+///
+/// Computes `to[q + s·(r·p + u)] = ω^(p·u) Σ_t from[q + s·(p + t·m)] ω_r^(t·u)`
+/// for `m = len / r`.
+fn pass(from: &[Field], to: &mut [Field], radix: usize, len: usize, stride: usize, root: Field) {
+    let m = len / radix;
+    let s = stride;
+    let root_r = root.pow(m as u64);
+    let mut twiddle = Field::from(1);
+    match radix {
+        2 => {
+            for p in 0..m {
+                for q in 0..s {
+                    let a = from[q + s * p];
+                    let b = from[q + s * (p + m)];
+                    to[q + s * 2 * p] = a + b;
+                    to[q + s * (2 * p + 1)] = (a - b) * twiddle;
+                }
+                twiddle *= root;
+            }
+        }
+        4 => {
+            for p in 0..m {
+                let twiddle_2 = twiddle * twiddle;
+                let twiddle_3 = twiddle_2 * twiddle;
+                for q in 0..s {
+                    let a0 = from[q + s * p];
+                    let a1 = from[q + s * (p + m)];
+                    let a2 = from[q + s * (p + 2 * m)];
+                    let a3 = from[q + s * (p + 3 * m)];
+                    let t0 = a0 + a2;
+                    let t1 = a0 - a2;
+                    let t2 = a1 + a3;
+                    let t3 = (a1 - a3) * root_r;
+                    to[q + s * 4 * p] = t0 + t2;
+                    to[q + s * (4 * p + 1)] = (t1 + t3) * twiddle;
+                    to[q + s * (4 * p + 2)] = (t0 - t2) * twiddle_2;
+                    to[q + s * (4 * p + 3)] = (t1 - t3) * twiddle_3;
+                }
+                twiddle *= root;
+            }
+        }
+        _ => {
+            let roots = (0..radix)
+                .scan(Field::from(1), |omega, _| {
+                    let result = *omega;
+                    *omega *= root_r;
+                    Some(result)
+                })
+                .collect::<Vec<_>>();
+            for p in 0..m {
+                for q in 0..s {
+                    let mut omega_u = Field::from(1);
+                    for u in 0..radix {
+                        let mut sum = Field::from(0);
+                        for t in 0..radix {
+                            sum += from[q + s * (p + t * m)] * roots[(t * u) % radix];
+                        }
+                        to[q + s * (radix * p + u)] = sum * omega_u;
+                        omega_u *= twiddle;
+                    }
+                }
+                twiddle *= root;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::naive, *};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_stockham() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [1, 2, 3, 4, 8, 12, 16, 60, 240, 256, 512, 257, 1020] {
+            let input = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = input.clone();
+            naive::ntt(&mut expected);
+            let mut output = vec![Field::from(0); size];
+            ntt(&input, &mut output);
+            assert_eq!(output, expected);
+        }
+    }
+}
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::{
+        super::{cooley_tukey::CooleyTukey, Ntt},
+        *,
+    };
+    use crate::bench::rand_vec;
+    use criterion::{BenchmarkId, Criterion, Throughput};
+
+    pub fn group(criterion: &mut Criterion) {
+        bench_stockham(criterion, 256, 256);
+        bench_stockham(criterion, 1024, 1024);
+    }
+
+    fn bench_stockham(criterion: &mut Criterion, a: usize, b: usize) {
+        let size = a * b;
+        let input: Vec<Field> = rand_vec(size);
+        let mut output = vec![Field::from(0); size];
+        let mut group = criterion.benchmark_group("ntt/stockham");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("stockham", size), |bencher| {
+            bencher.iter(|| ntt(&input, &mut output));
+        });
+        let cooley_tukey = CooleyTukey::new(a, b);
+        group.bench_function(BenchmarkId::new("cooley_tukey", size), |bencher| {
+            bencher.iter(|| cooley_tukey.ntt(&mut output));
+        });
+    }
+}
@@ -0,0 +1,160 @@
+//! Cache-friendly in-place bit reversal.
+//!
+//! Uses the COBRA blocked algorithm of Carter and Gatlin: an index of `L`
+//! bits is split as `(a, b, c)` with `a` and `c` of `q` bits. Its reversal is
+//! `(rev c, rev b, rev a)`, so the `B × B` block of all `(a, c)` for a fixed
+//! middle `b` maps onto the block for `rev b`, transposed. Both blocks are
+//! staged in buffers, such that all reads and writes of the array are
+//! contiguous runs of `B` elements.
+
+use super::{permutation, Permute};
+use std::ptr::swap;
+
+/// Log of the block side `B`, such that two `B × B` buffers of `u64` fit in
+/// L1.
+const LOG_BLOCK: u32 = 5;
+
+/// Sizes below this use a simple loop of swaps.
+pub(super) const MIN_SIZE: usize = 1 << (2 * LOG_BLOCK + 2);
+
+pub struct BitReverse(usize);
+
+impl BitReverse {
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two.
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        assert!(size.is_power_of_two());
+        Self(size)
+    }
+}
+
+impl<T: 'static + Copy + Send + Sync> Permute<T> for BitReverse {
+    fn len(&self) -> usize {
+        self.0
+    }
+
+    fn permute(&self, values: &mut [T]) {
+        assert_eq!(values.len(), self.0);
+        cobra(values);
+    }
+
+    fn permute_inverse(&self, values: &mut [T]) {
+        self.permute(values);
+    }
+}
+
+/// Bit-reverse permute an array in place, see the module documentation.
+///
+/// # Panics
+///
+/// Panics if the length is not a power of two.
+pub fn cobra<T: Copy>(values: &mut [T]) {
+    let size = values.len();
+    assert!(size.is_power_of_two());
+    if size < MIN_SIZE {
+        swaps(values);
+        return;
+    }
+    let q = LOG_BLOCK;
+    let block = 1 << q;
+    let middle = size >> (2 * q);
+    let shift = size.trailing_zeros() - q;
+    let reverse_q = permutation::bit_reverse(block);
+    let reverse_m = permutation::bit_reverse(middle);
+
+    let mut first = Vec::with_capacity(block * block);
+    let mut second = Vec::with_capacity(block * block);
+    for b in 0..middle {
+        let rb = reverse_m(b);
+        if rb < b {
+            continue;
+        }
+        // Stage `x[a, b, c]` as `first[a · B + c]`, and the same for `rev b`.
+        first.clear();
+        second.clear();
+        for a in 0..block {
+            let start = (a << shift) | (b << q);
+            first.extend_from_slice(&values[start..start + block]);
+            if rb != b {
+                let start = (a << shift) | (rb << q);
+                second.extend_from_slice(&values[start..start + block]);
+            }
+        }
+        // Write `x[c', rev b, a'] = first[rev a', rev c']` and vice versa.
+        for c in 0..block {
+            let rc = reverse_q(c);
+            let start = (c << shift) | (rb << q);
+            for (a, value) in values[start..start + block].iter_mut().enumerate() {
+                *value = first[reverse_q(a) * block + rc];
+            }
+            if rb != b {
+                let start = (c << shift) | (b << q);
+                for (a, value) in values[start..start + block].iter_mut().enumerate() {
+                    *value = second[reverse_q(a) * block + rc];
+                }
+            }
+        }
+    }
+}
+
+/// Bit-reverse permute by swapping each pair `i < rev i`.
+fn swaps<T: Copy>(values: &mut [T]) {
+    let reverse = permutation::bit_reverse(values.len());
+    let ptr = values.as_mut_ptr();
+    for i in 0..values.len() {
+        let j = reverse(i);
+        if i < j {
+            // Safety: `i` and `j` are distinct and in bounds.
+            unsafe { swap(ptr.add(i), ptr.add(j)) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cobra() {
+        for log_size in 0..=16 {
+            let size = 1_usize << log_size;
+            let input = (0..size).collect::<Vec<_>>();
+            let mut values = input.clone();
+            cobra(&mut values);
+            let reverse = permutation::bit_reverse(size);
+            for (i, &value) in values.iter().enumerate() {
+                assert_eq!(value, reverse(i));
+            }
+            BitReverse::new(size).permute(&mut values);
+            assert_eq!(values, input);
+        }
+    }
+}
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::{super::bit_reverse, *};
+    use criterion::{BenchmarkId, Criterion, Throughput};
+
+    pub fn group(criterion: &mut Criterion) {
+        bench_cobra(criterion, 1 << 16);
+        bench_cobra(criterion, 1 << 20);
+        bench_cobra(criterion, 1 << 24);
+    }
+
+    /// Blocked in place versus [`super::super::bit_reverse`].
+    fn bench_cobra(criterion: &mut Criterion, size: usize) {
+        let mut values = (0_u64..size as u64).collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("permute/cobra");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("cobra", size), |bencher| {
+            bencher.iter(|| cobra(&mut values));
+        });
+        group.bench_function(BenchmarkId::new("bit_reverse", size), |bencher| {
+            bencher.iter(|| bit_reverse(&mut values));
+        });
+    }
+}
@@ -7,13 +7,46 @@ use std::{
 pub fn transpose<T: Copy + Send + Sync>(matrix: &mut [T], (rows, cols): (usize, usize)) {
     // eprintln!("copy::transpose({rows}, {cols})");
     assert_eq!(matrix.len(), rows * cols);
-    if matrix.is_empty() {
+    let copy = matrix.to_vec();
+    transpose_to(&copy, matrix, (rows, cols));
+}
+
+/// Write the transpose of the `rows × cols` matrix `src` to `dst`.
+///
+/// Works on `BLOCK × BLOCK` tiles such that both the reads and the writes of
+/// a tile stay in cache. Bands of `BLOCK` output rows are done in parallel.
+///
+/// # Panics
+///
+/// Panics if either length does not equal `rows * cols`.
+pub fn transpose_to<T: Copy + Send + Sync>(src: &[T], dst: &mut [T], (rows, cols): (usize, usize)) {
+    const BLOCK: usize = 32;
+    const PAR_THRESHOLD: usize = 1 << 17;
+    assert_eq!(src.len(), rows * cols);
+    assert_eq!(dst.len(), rows * cols);
+    if src.is_empty() {
         return;
     }
-    let q = matrix.len() - 1;
-    let inv_permute = |i| if i == q { q } else { i * cols % q };
-    let copy = matrix.to_vec();
-    permute_wo_oop(&copy, matrix, 1, inv_permute);
+
+    // Output rows `j0..j0 + BLOCK` are the input columns.
+    let band = |(index, band): (usize, &mut [T])| {
+        let j0 = index * BLOCK;
+        let width = band.len() / rows;
+        for i0 in (0..rows).step_by(BLOCK) {
+            let height = BLOCK.min(rows - i0);
+            for j in 0..width {
+                let out = &mut band[j * rows + i0..j * rows + i0 + height];
+                for (i, value) in out.iter_mut().enumerate() {
+                    *value = src[(i0 + i) * cols + j0 + j];
+                }
+            }
+        }
+    };
+    if src.len() < PAR_THRESHOLD {
+        dst.chunks_mut(BLOCK * rows).enumerate().for_each(band);
+    } else {
+        dst.par_chunks_mut(BLOCK * rows).enumerate().for_each(band);
+    }
 }
 
 pub fn transpose_copy_wo(matrix: &mut [u64], width: usize, height: usize) {
@@ -138,6 +171,18 @@ mod tests {
     use super::{super::transpose_copy, *};
     use proptest::prelude::*;
 
+    #[test]
+    fn test_transpose_to() {
+        for (rows, cols) in [(0, 3), (1, 1), (2, 3), (17, 20), (64, 32)] {
+            let src = (0..rows * cols).collect::<Vec<_>>();
+            let mut dst = vec![0; src.len()];
+            transpose_to(&src, &mut dst, (rows, cols));
+            let mut expected = src.clone();
+            transpose_copy(&mut expected, (rows, cols));
+            assert_eq!(dst, expected);
+        }
+    }
+
     #[test]
     #[rustfmt::skip]
     fn test_transpose_copy_wo() {
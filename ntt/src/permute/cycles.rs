@@ -132,15 +132,16 @@ impl<I: Index> Cycles<I> {
         }
     }
 
-    fn apply<T: Copy + Send + Sync>(&self, values: &mut [T]) {
+    /// Apply the permutation, or its inverse by walking the cycles backwards.
+    fn apply<T: Copy + Send + Sync>(&self, values: &mut [T], inverse: bool) {
         if self.parallel {
-            self.par_apply(values);
+            self.par_apply(values, inverse);
         } else {
-            self.ser_apply(values);
+            self.ser_apply(values, inverse);
         }
     }
 
-    fn ser_apply<T: Copy + Send + Sync>(&self, values: &mut [T]) {
+    fn ser_apply<T: Copy + Send + Sync>(&self, values: &mut [T], inverse: bool) {
         assert_eq!(values.len(), self.size);
 
         // Cycles
@@ -148,22 +149,14 @@ impl<I: Index> Cycles<I> {
             debug_assert!(*length >= 2);
             debug_assert_eq!(cycles.len() % *length, 0);
             for cycle in cycles.chunks_exact(*length) {
-                unsafe {
-                    // SAFETY: We know cycles has length `length` >= 2 and contains
-                    // only valid non-overlapping indices into `values`.
-                    let mut dst = cycle.get_unchecked(0).to();
-                    let temp = *values.get_unchecked(dst);
-                    for src in cycle.iter().skip(1).map(|i| i.to()) {
-                        *values.get_unchecked_mut(dst) = *values.get_unchecked(src);
-                        dst = src;
-                    }
-                    *values.get_unchecked_mut(dst) = temp;
-                }
+                // SAFETY: We know cycles has length `length` >= 2 and contains
+                // only valid non-overlapping indices into `values`.
+                unsafe { rotate(values.as_mut_ptr(), cycle, inverse) };
             }
         }
     }
 
-    fn par_apply<T: Copy + Send + Sync>(&self, values: &mut [T]) {
+    fn par_apply<T: Copy + Send + Sync>(&self, values: &mut [T], inverse: bool) {
         assert_eq!(values.len(), self.size);
 
         // SAFETY: All cycles are disjoint, so we can safely move the values in
@@ -175,17 +168,9 @@ impl<I: Index> Cycles<I> {
             debug_assert_eq!(cycles.len() % *length, 0);
             cycles.par_chunks_exact(*length).for_each(|cycle| {
                 let values = values.load(Ordering::Relaxed);
-                unsafe {
-                    // SAFETY: We know cycles has length `length` >= 2 and contains
-                    // only valid non-overlapping indices into `values`.
-                    let mut dst = cycle.get_unchecked(0).to();
-                    let temp = *values.add(dst);
-                    for src in cycle.iter().skip(1).map(|i| i.to()) {
-                        *values.add(dst) = *values.add(src);
-                        dst = src;
-                    }
-                    *values.add(dst) = temp;
-                }
+                // SAFETY: We know cycles has length `length` >= 2 and contains
+                // only valid non-overlapping indices into `values`.
+                unsafe { rotate(values, cycle, inverse) };
             });
         });
     }
@@ -246,7 +231,33 @@ impl<I: Index, T: 'static + Copy + Send + Sync> Permute<T> for Cycles<I> {
     }
 
     fn permute(&self, values: &mut [T]) {
-        self.apply(values)
+        self.apply(values, false);
+    }
+
+    fn permute_inverse(&self, values: &mut [T]) {
+        self.apply(values, true);
+    }
+}
+
+/// Move each value one step along `cycle`, backwards if `inverse`.
+///
+/// # Safety
+///
+/// `cycle` must be non-empty and contain distinct valid indices into `values`.
+unsafe fn rotate<T: Copy, I: Index>(values: *mut T, cycle: &[I], inverse: bool) {
+    unsafe fn rotate_by<T: Copy>(values: *mut T, mut cycle: impl Iterator<Item = usize>) {
+        let mut dst = cycle.next().unwrap_unchecked();
+        let temp = *values.add(dst);
+        for src in cycle {
+            *values.add(dst) = *values.add(src);
+            dst = src;
+        }
+        *values.add(dst) = temp;
+    }
+    if inverse {
+        rotate_by(values, cycle.iter().rev().map(|i| i.to()));
+    } else {
+        rotate_by(values, cycle.iter().map(|i| i.to()));
     }
 }
 
@@ -92,12 +92,21 @@ impl<T: 'static + Copy + Send + Sync> Permute<T> for Gcd {
             self.cycles.par_apply_chunks(values, self.square);
         }
     }
+
+    fn permute_inverse(&self, values: &mut [T]) {
+        assert_eq!(values.len(), <Self as Permute<T>>::len(self));
+        let (rows, cols) = (self.rows * self.square, self.cols * self.square);
+        super::transpose(values, (cols, rows));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{super::copy, *};
-    use crate::divisors::{divisors, split};
+    use crate::{
+        divisors::{divisors, split},
+        test_utils::assert_is_permutation,
+    };
 
     #[test]
     fn test_transpose_4_6() {
@@ -138,6 +147,7 @@ mod tests {
         let mut matrix = (0_u64..size as u64).collect::<Vec<_>>();
         let mut expected = matrix.clone();
         Gcd::new(rows, cols).permute(&mut matrix);
+        assert_is_permutation(&expected, &matrix);
         copy::transpose(&mut expected, (rows, cols));
         assert_eq!(matrix, expected);
     }
@@ -2,23 +2,51 @@
 //! Concurrency and Computation Practice Experience 2019
 //! <https://doi.org/10.1002/cpe.5071>
 
-use std::{cmp::min, sync::Arc};
+use std::{
+    cmp::min,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use super::{copy, square, Permute};
 
+/// Default for [`par_threshold`].
+pub const DEFAULT_PAR_THRESHOLD: usize = 1 << 17;
+
+static PAR_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_PAR_THRESHOLD);
+
+/// Number of elements from which transposes split their work with
+/// `rayon::join`.
+///
+/// The shuffles that merge the pieces use eight times this.
+pub fn par_threshold() -> usize {
+    PAR_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Override [`par_threshold`] for all subsequent transposes.
+///
+/// Use `usize::MAX` to always run serially, for example when already called
+/// from parallel code. [`Gw18`] reads it on every transpose, so this also
+/// applies to cached strategies.
+pub fn set_par_threshold(threshold: usize) {
+    PAR_THRESHOLD.store(threshold, Ordering::Relaxed);
+}
+
 pub struct Gw18<T: 'static + Copy + Send + Sync> {
     rows:                usize,
     cols:                usize,
     square:              usize,
     transpose_square:    Arc<dyn Permute<T>>,
-    transpose_remainder: Arc<dyn Permute<T>>,
-    parallel:            bool,
+    // `None` if the squares tile the matrix, e.g. for `rows == cols`.
+    transpose_remainder: Option<Arc<dyn Permute<T>>>,
 }
 
 impl<T: 'static + Copy + Send + Sync> Gw18<T> {
     pub fn new((rows, cols): (usize, usize)) -> Self {
         assert!(rows > 1 && cols > 1);
-        let size = rows * cols;
+        checked_size(rows, cols);
 
         let (square, remainder) = if rows > cols {
             (cols, (rows % cols, cols))
@@ -26,7 +54,8 @@ impl<T: 'static + Copy + Send + Sync> Gw18<T> {
             (rows, (rows, cols % rows))
         };
         let transpose_square = super::transpose_strategy((square, square));
-        let transpose_remainder = super::transpose_strategy(remainder);
+        let transpose_remainder =
+            (remainder.0 * remainder.1 > 0).then(|| super::transpose_strategy(remainder));
 
         Self {
             rows,
@@ -34,10 +63,13 @@ impl<T: 'static + Copy + Send + Sync> Gw18<T> {
             square,
             transpose_square,
             transpose_remainder,
-            parallel: size > 1 << 17,
         }
     }
 
+    fn parallel(&self) -> bool {
+        self.rows * self.cols > par_threshold()
+    }
+
     fn transpose_join(&self, values: &mut [T], blocks: usize) {
         let size = self.square;
 
@@ -49,13 +81,13 @@ impl<T: 'static + Copy + Send + Sync> Gw18<T> {
             let blocks_bottom = blocks - blocks_top;
             let (top, bottom) = values.split_at_mut(blocks_top * size * size);
 
-            if self.parallel {
-                transpose_join(top, blocks_top, size);
-                transpose_join(bottom, blocks_bottom, size);
+            if self.parallel() {
+                transpose_join(top, blocks_top, size, square::transpose);
+                transpose_join(bottom, blocks_bottom, size, square::transpose);
             } else {
                 rayon::join(
-                    || transpose_join(top, blocks_top, size),
-                    || transpose_join(bottom, blocks_bottom, size),
+                    || transpose_join(top, blocks_top, size, square::transpose),
+                    || transpose_join(bottom, blocks_bottom, size, square::transpose),
                 );
             }
 
@@ -72,91 +104,134 @@ impl<T: 'static + Copy + Send + Sync> Permute<T> for Gw18<T> {
 
     fn permute(&self, values: &mut [T]) {
         let (rows, cols) = (self.rows, self.cols);
-        assert_eq!(values.len(), rows * cols);
+        assert_eq!(values.len(), checked_size(rows, cols));
 
         if self.rows > self.cols {
             // Divide into (cols × cols) squares and remainder
             let (squares, remainder) = (rows / cols, rows % cols);
-            if remainder == 0 {
-                transpose_join(values, squares, cols);
-            } else {
+            if let Some(transpose_remainder) = &self.transpose_remainder {
                 let (head, tail) = values.split_at_mut(squares * cols * cols);
 
-                if !self.parallel {
+                if !self.parallel() {
                     // Transpose and join squares
-                    transpose_join(head, squares, cols);
+                    transpose_join(head, squares, cols, square::transpose);
 
                     // Transpose remainder
-                    self.transpose_remainder.permute(tail);
+                    transpose_remainder.permute(tail);
                 } else {
                     rayon::join(
-                        || transpose_join(head, squares, cols),
-                        || self.transpose_remainder.permute(tail),
+                        || transpose_join(head, squares, cols, square::transpose),
+                        || transpose_remainder.permute(tail),
                     );
                 }
 
                 // Merge remainder into big matrix.
                 shuffle(values, squares * cols, remainder, cols);
+            } else {
+                transpose_join(values, squares, cols, square::transpose);
             }
         } else {
             // Divide into (rows × rows) squares and remainder
             let (squares, remainder) = (cols / rows, cols % rows);
-            if remainder == 0 {
-                partition_transpose(values, squares, rows);
-            } else {
+            if let Some(transpose_remainder) = &self.transpose_remainder {
                 // Split remainder from matrix.
                 unshuffle(values, squares * rows, remainder, rows);
                 let (head, tail) = values.split_at_mut(squares * rows * rows);
 
-                if !self.parallel {
+                if !self.parallel() {
                     // Partition and transpose the squares
-                    partition_transpose(head, squares, rows);
+                    partition_transpose(head, squares, rows, square::transpose);
 
                     // Transpose remainder
-                    self.transpose_remainder.permute(tail);
+                    transpose_remainder.permute(tail);
                 } else {
                     rayon::join(
-                        || partition_transpose(head, squares, rows),
-                        || self.transpose_remainder.permute(tail),
+                        || partition_transpose(head, squares, rows, square::transpose),
+                        || transpose_remainder.permute(tail),
                     );
                 }
+            } else {
+                partition_transpose(values, squares, rows, square::transpose);
             }
         }
     }
+
+    fn permute_inverse(&self, values: &mut [T]) {
+        assert_eq!(values.len(), checked_size(self.rows, self.cols));
+        transpose(values, (self.cols, self.rows));
+    }
 }
 
 pub fn transpose<T: Copy + Send + Sync>(values: &mut [T], (rows, cols): (usize, usize)) {
     const COPY_THRESHOLD: usize = if !cfg!(test) { 1 << 14 } else { 4 };
-    const PAR_THRESHOLD: usize = 1 << 17;
 
     // eprintln!("transpose({rows}, {cols})");
-    assert_eq!(values.len(), rows * cols);
-    let size = rows * cols;
+    let size = checked_size(rows, cols);
+    assert_eq!(values.len(), size);
 
     if rows <= 1 || cols <= 1 {
         return;
-    } else if rows == cols {
-        square::transpose(values, rows)
+    }
+    if rows == cols {
+        square::transpose(values, rows);
     } else if size <= COPY_THRESHOLD {
         copy::transpose(values, (rows, cols));
-    } else if rows > cols {
+    } else {
+        transpose_rec(values, (rows, cols), square::transpose, transpose);
+    }
+}
+
+/// Transpose a matrix in place for types that are not `Copy`, such as
+/// `String` or `Vec`.
+///
+/// The same algorithm as [`transpose`], except that elements are only moved by
+/// swaps. There is no copying base case, so nothing is duplicated or dropped.
+///
+/// # Panics
+///
+/// Panics if `values.len()` does not equal `rows * cols`.
+pub fn transpose_move<T: Send>(values: &mut [T], (rows, cols): (usize, usize)) {
+    let size = checked_size(rows, cols);
+    assert_eq!(values.len(), size);
+
+    if rows <= 1 || cols <= 1 {
+        return;
+    }
+    if rows == cols {
+        square::transpose_move(values, rows);
+    } else {
+        transpose_rec(values, (rows, cols), square::transpose_move, transpose_move);
+    }
+}
+
+/// Transpose a non-square matrix by splitting off squares, using `square` for
+/// the squares and `recurse` for the remainder.
+fn transpose_rec<T: Send>(
+    values: &mut [T],
+    (rows, cols): (usize, usize),
+    square: fn(&mut [T], usize),
+    recurse: fn(&mut [T], (usize, usize)),
+) {
+    let size = values.len();
+
+    if rows > cols {
         // Divide into (cols × cols) squares and remainder
         let (squares, remainder) = (rows / cols, rows % cols);
         if remainder == 0 {
-            transpose_join(values, squares, cols);
+            transpose_join(values, squares, cols, square);
         } else {
             let (head, tail) = values.split_at_mut(squares * cols * cols);
 
-            if size < PAR_THRESHOLD {
+            if size < par_threshold() {
                 // Transpose and join squares
-                transpose_join(head, squares, cols);
+                transpose_join(head, squares, cols, square);
 
                 // Transpose remainder
-                transpose(tail, (remainder, cols));
+                recurse(tail, (remainder, cols));
             } else {
                 rayon::join(
-                    || transpose_join(head, squares, cols),
-                    || transpose(tail, (remainder, cols)),
+                    || transpose_join(head, squares, cols, square),
+                    || recurse(tail, (remainder, cols)),
                 );
             }
 
@@ -167,50 +242,61 @@ pub fn transpose<T: Copy + Send + Sync>(values: &mut [T], (rows, cols): (usize,
         // Divide into (rows × rows) squares and remainder
         let (squares, remainder) = (cols / rows, cols % rows);
         if remainder == 0 {
-            partition_transpose(values, squares, rows);
+            partition_transpose(values, squares, rows, square);
         } else {
             // Split remainder from matrix.
             unshuffle(values, squares * rows, remainder, rows);
             let (head, tail) = values.split_at_mut(squares * rows * rows);
 
-            if size < PAR_THRESHOLD {
+            if size < par_threshold() {
                 // Partition and transpose the squares
-                partition_transpose(head, squares, rows);
+                partition_transpose(head, squares, rows, square);
 
                 // Transpose remainder
-                transpose(tail, (rows, remainder));
+                recurse(tail, (rows, remainder));
             } else {
                 rayon::join(
-                    || partition_transpose(head, squares, rows),
-                    || transpose(tail, (rows, remainder)),
+                    || partition_transpose(head, squares, rows, square),
+                    || recurse(tail, (rows, remainder)),
                 );
             }
         }
     }
 }
 
-fn transpose_join<T: Copy + Send>(values: &mut [T], blocks: usize, size: usize) {
-    const PAR_THRESHOLD: usize = 1 << 17;
-    // eprintln!("transpose_join({blocks}, {size})");
-    let n = blocks * size * size;
+/// Product of matrix dimensions, panicking instead of wrapping on overflow.
+/// This can happen on 32-bit targets.
+fn checked_size(rows: usize, cols: usize) -> usize {
+    rows.checked_mul(cols)
+        .unwrap_or_else(|| panic!("Matrix size {rows} × {cols} overflows usize."))
+}
+
+fn transpose_join<T: Send>(
+    values: &mut [T],
+    blocks: usize,
+    size: usize,
+    square: fn(&mut [T], usize),
+) {
+    // eprintln!("transpose_join({blocks}, {size}, square)");
+    let n = checked_size(blocks, checked_size(size, size));
     debug_assert_eq!(values.len(), n);
     debug_assert!(!values.is_empty());
 
     if blocks == 1 {
-        square::transpose(values, size);
+        square(values, size);
     } else {
         // Recurse by splitting into two halves
         let blocks_top = blocks / 2;
         let blocks_bottom = blocks - blocks_top;
         let (top, bottom) = values.split_at_mut(blocks_top * size * size);
 
-        if n < PAR_THRESHOLD {
-            transpose_join(top, blocks_top, size);
-            transpose_join(bottom, blocks_bottom, size);
+        if n < par_threshold() {
+            transpose_join(top, blocks_top, size, square);
+            transpose_join(bottom, blocks_bottom, size, square);
         } else {
             rayon::join(
-                || transpose_join(top, blocks_top, size),
-                || transpose_join(bottom, blocks_bottom, size),
+                || transpose_join(top, blocks_top, size, square),
+                || transpose_join(bottom, blocks_bottom, size, square),
             );
         }
 
@@ -219,15 +305,19 @@ fn transpose_join<T: Copy + Send>(values: &mut [T], blocks: usize, size: usize)
     }
 }
 
-fn partition_transpose<T: Copy + Send>(values: &mut [T], blocks: usize, size: usize) {
-    const PAR_THRESHOLD: usize = 1 << 17;
+fn partition_transpose<T: Send>(
+    values: &mut [T],
+    blocks: usize,
+    size: usize,
+    square: fn(&mut [T], usize),
+) {
     // eprintln!("partition({blocks}, {size})");
-    let n = blocks * size * size;
+    let n = checked_size(blocks, checked_size(size, size));
     debug_assert_eq!(values.len(), n);
     debug_assert!(!values.is_empty());
 
     if blocks == 1 {
-        square::transpose(values, size);
+        square(values, size);
     } else {
         // Recurse by splitting into two halves
         let blocks_top = blocks / 2;
@@ -237,21 +327,20 @@ fn partition_transpose<T: Copy + Send>(values: &mut [T], blocks: usize, size: us
         unshuffle(values, blocks_top * size, blocks_bottom * size, size);
         let (top, bottom) = values.split_at_mut(blocks_top * size * size);
 
-        if n < PAR_THRESHOLD {
-            partition_transpose(top, blocks_top, size);
-            partition_transpose(bottom, blocks_bottom, size);
+        if n < par_threshold() {
+            partition_transpose(top, blocks_top, size, square);
+            partition_transpose(bottom, blocks_bottom, size, square);
         } else {
             rayon::join(
-                || partition_transpose(top, blocks_top, size),
-                || partition_transpose(bottom, blocks_bottom, size),
+                || partition_transpose(top, blocks_top, size, square),
+                || partition_transpose(bottom, blocks_bottom, size, square),
             );
         }
     }
 }
 
 ///
-fn shuffle<T: Copy + Send>(values: &mut [T], a: usize, b: usize, m: usize) {
-    const PAR_THRESHOLD: usize = 1 << 20;
+fn shuffle<T: Send>(values: &mut [T], a: usize, b: usize, m: usize) {
     // eprintln!("shuffle({la}, {lb}, {m})");
     debug_assert_eq!(values.len(), (a + b) * m);
     let size = values.len();
@@ -272,7 +361,7 @@ fn shuffle<T: Copy + Send>(values: &mut [T], a: usize, b: usize, m: usize) {
     let (left, right) = values.split_at_mut((a + b) * m_left);
 
     // Recurse
-    if size < PAR_THRESHOLD {
+    if size < par_threshold().saturating_mul(8) {
         shuffle(left, a, b, m_left);
         shuffle(right, a, b, m_right);
     } else {
@@ -285,8 +374,7 @@ fn shuffle<T: Copy + Send>(values: &mut [T], a: usize, b: usize, m: usize) {
 
 /// Given a vector of length `(a + b) * m` in pattern (a ‖ b) * m unshuffle it
 /// into a a pattern (a * m ‖ b * m).
-fn unshuffle<T: Copy + Send>(values: &mut [T], a: usize, b: usize, m: usize) {
-    const PAR_THRESHOLD: usize = 1 << 20;
+fn unshuffle<T: Send>(values: &mut [T], a: usize, b: usize, m: usize) {
     // eprintln!("unshuffle({a}, {b}, {m})");
     debug_assert_eq!(values.len(), (a + b) * m);
     let size = values.len();
@@ -302,7 +390,7 @@ fn unshuffle<T: Copy + Send>(values: &mut [T], a: usize, b: usize, m: usize) {
     let m_right = m - m_left;
     let (left, right) = values.split_at_mut((a + b) * m_left);
 
-    if size < PAR_THRESHOLD {
+    if size < par_threshold().saturating_mul(8) {
         unshuffle(left, a, b, m_left);
         unshuffle(right, a, b, m_right);
     } else {
@@ -320,33 +408,71 @@ fn unshuffle<T: Copy + Send>(values: &mut [T], a: usize, b: usize, m: usize) {
 }
 
 /// Given a pattern (a ‖ b) turn it into (b ‖ a).
-fn exchange<T: Copy>(values: &mut [T], a: usize, b: usize) {
+fn exchange<T>(mut values: &mut [T], mut a: usize, mut b: usize) {
     // eprintln!("exchange({a}, {b})");
     debug_assert_eq!(values.len(), a + b);
-    if a == b {
-        let (left, right) = values.split_at_mut(a);
-        left.swap_with_slice(right);
-    } else if a > b {
-        let (left, remainder) = values.split_at_mut(b);
-        let (_middle, right) = remainder.split_at_mut(a - b);
-        left.swap_with_slice(right);
-        exchange(&mut values[b..], a - b, b);
-    } else {
-        let (left, remainder) = values.split_at_mut(a);
-        let (_middle, right) = remainder.split_at_mut(b - a);
-        left.swap_with_slice(right);
-        exchange(&mut values[..b], a, b - a);
+    // Iterative, the recursion depth is up to `max(a, b) / min(a, b)`.
+    loop {
+        if a == b {
+            let (left, right) = values.split_at_mut(a);
+            left.swap_with_slice(right);
+            return;
+        } else if a > b {
+            let (left, remainder) = values.split_at_mut(b);
+            let (_middle, right) = remainder.split_at_mut(a - b);
+            left.swap_with_slice(right);
+            values = &mut values[b..];
+            a -= b;
+        } else {
+            let (left, remainder) = values.split_at_mut(a);
+            let (_middle, right) = remainder.split_at_mut(b - a);
+            left.swap_with_slice(right);
+            values = &mut values[..b];
+            b -= a;
+        }
     }
 }
 
+/// Largest power of two strictly less than `m`, or `1` for `m <= 2`.
 fn largest_power_of_two(m: usize) -> usize {
-    2_usize.pow((m as f64 - 1.).log2() as u32)
+    let below = m.saturating_sub(1).max(1);
+    1 << (usize::BITS - 1 - below.leading_zeros())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::divisors::{divisors, split};
+    use crate::{
+        divisors::{divisors, split},
+        test_utils::assert_is_permutation,
+    };
+    use proptest::proptest;
+
+    #[test]
+    #[should_panic(expected = "overflows usize")]
+    fn test_transpose_overflow() {
+        // Without the check this wraps to 0 and accepts the empty slice.
+        let half = 1 << (usize::BITS / 2);
+        transpose::<u64>(&mut [], (half, half));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows usize")]
+    fn test_new_overflow() {
+        let _ = Gw18::<u64>::new((usize::MAX / 2, 3));
+    }
+
+    #[test]
+    fn test_largest_power_of_two() {
+        let mut expected = 1;
+        for m in 1..(1 << 22) + 3 {
+            if 2 * expected < m {
+                expected *= 2;
+            }
+            assert_eq!(largest_power_of_two(m), expected, "m = {m}");
+        }
+        assert_eq!(largest_power_of_two(usize::MAX), 1 << (usize::BITS - 1));
+    }
 
     #[test]
     fn test_transpose_32_16() {
@@ -377,11 +503,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_transpose_move() {
+        for (rows, cols) in [(1, 5), (3, 3), (5, 3), (17, 20), (32, 16), (100, 7)] {
+            let mut matrix = (0..rows * cols).map(|i| i.to_string()).collect::<Vec<_>>();
+            transpose_move(&mut matrix, (rows, cols));
+            for i in 0..rows {
+                for j in 0..cols {
+                    assert_eq!(matrix[j * rows + i], (i * cols + j).to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_transpose_fuzz() {
+        // Mostly sizes with remainders in the square decomposition.
+        proptest!(|(rows in 1_usize..300, cols in 1_usize..300)| {
+            test_size(rows, cols);
+        });
+    }
+
+    #[test]
+    fn test_new_no_remainder() {
+        for (rows, cols) in [(8, 8), (32, 32), (8, 24), (48, 16)] {
+            let mut matrix = (0..rows * cols).collect::<Vec<_>>();
+            let mut reference = matrix.clone();
+            Gw18::new((rows, cols)).permute(&mut matrix);
+            copy::transpose(&mut reference, (rows, cols));
+            assert_eq!(matrix, reference, "{rows} × {cols}");
+        }
+    }
+
+    #[test]
+    fn test_par_threshold() {
+        // Other tests may run meanwhile, the threshold does not affect results.
+        for threshold in [usize::MAX, 0] {
+            set_par_threshold(threshold);
+            for (rows, cols) in [(17, 20), (300, 7), (640, 300), (300, 640)] {
+                test_size(rows, cols);
+                let mut matrix = (0..rows * cols).collect::<Vec<_>>();
+                let mut reference = matrix.clone();
+                Gw18::new((rows, cols)).permute(&mut matrix);
+                copy::transpose(&mut reference, (rows, cols));
+                assert_eq!(matrix, reference);
+            }
+        }
+        set_par_threshold(DEFAULT_PAR_THRESHOLD);
+    }
+
     fn test_size(rows: usize, cols: usize) {
         let size = rows * cols;
         let mut matrix = (0_u64..size as u64).collect::<Vec<_>>();
         let mut reference = matrix.clone();
         transpose(&mut matrix, (rows, cols));
+        assert_is_permutation(&reference, &matrix);
         copy::transpose(&mut reference, (rows, cols));
         assert_eq!(matrix, reference);
     }
@@ -1,3 +1,4 @@
+pub mod bit_reverse;
 mod copy;
 pub mod cycles;
 pub mod gcd;
@@ -6,12 +7,31 @@ pub mod permutation;
 mod square;
 
 pub use self::{copy::*, square::*};
-use std::sync::Arc;
+use crate::Field;
+use rayon::prelude::*;
+use std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+    ptr,
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Transpose strategies by element type and shape. The values are
+/// `Arc<dyn Permute<T>>` for the `T` of the key.
+type TransposeCache = BTreeMap<(TypeId, usize, usize), Arc<dyn Any + Send + Sync>>;
+
+static TRANSPOSE_CACHE: Mutex<TransposeCache> = Mutex::new(BTreeMap::new());
 
 pub trait Permute<T: 'static + Copy + Send + Sync>: Sync + Send {
     fn len(&self) -> usize;
 
     fn permute(&self, values: &mut [T]);
+
+    /// Undo [`Permute::permute`].
+    fn permute_inverse(&self, values: &mut [T]);
 }
 
 impl<T: 'static + Copy + Send + Sync> Permute<T> for Arc<dyn Permute<T>> {
@@ -22,11 +42,86 @@ impl<T: 'static + Copy + Send + Sync> Permute<T> for Arc<dyn Permute<T>> {
     fn permute(&self, values: &mut [T]) {
         self.as_ref().permute(values)
     }
+
+    fn permute_inverse(&self, values: &mut [T]) {
+        self.as_ref().permute_inverse(values);
+    }
+}
+
+/// Apply `first` and then `second` as one permutation.
+pub struct Compose<T: 'static + Copy + Send + Sync> {
+    first:  Arc<dyn Permute<T>>,
+    second: Arc<dyn Permute<T>>,
+}
+
+impl<T: 'static + Copy + Send + Sync> Compose<T> {
+    /// Compose two permutations, `first` is applied first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two permutations have different lengths.
+    pub fn new(first: Arc<dyn Permute<T>>, second: Arc<dyn Permute<T>>) -> Self {
+        assert_eq!(
+            first.len(),
+            second.len(),
+            "Composed permutations must have the same length."
+        );
+        Self { first, second }
+    }
+}
+
+impl<T: 'static + Copy + Send + Sync> Permute<T> for Compose<T> {
+    fn len(&self) -> usize {
+        self.first.len()
+    }
+
+    fn permute(&self, values: &mut [T]) {
+        assert_eq!(values.len(), self.len());
+        self.first.permute(values);
+        self.second.permute(values);
+    }
+
+    fn permute_inverse(&self, values: &mut [T]) {
+        assert_eq!(values.len(), self.len());
+        self.second.permute_inverse(values);
+        self.first.permute_inverse(values);
+    }
 }
 
-/// Generate a strategy for transposing matrices of the given size.
+/// Strategy for transposing matrices of the given size.
+///
+/// Strategies are built once per element type and shape and then shared, so
+/// repeated plans for the same shape are cheap. Use [`clear_transpose_cache`]
+/// to free them.
 pub fn transpose_strategy<T: 'static + Copy + Send + Sync>(
     (rows, cols): (usize, usize),
+) -> Arc<dyn Permute<T> + 'static> {
+    let key = (TypeId::of::<T>(), rows, cols);
+    let lock = TRANSPOSE_CACHE.lock().unwrap();
+    if let Some(strategy) = lock.get(&key) {
+        return strategy
+            .downcast_ref::<Arc<dyn Permute<T>>>()
+            .unwrap()
+            .clone();
+    }
+    // Building may recurse into this function, so do not hold the lock.
+    drop(lock);
+
+    let strategy = build_transpose_strategy((rows, cols));
+    let mut lock = TRANSPOSE_CACHE.lock().unwrap();
+    lock.insert(key, Arc::new(strategy.clone()));
+    strategy
+}
+
+/// Clear the cache of [`transpose_strategy`]. Also done by
+/// [`crate::ntt::clear_cache`].
+pub fn clear_transpose_cache() {
+    let mut lock = TRANSPOSE_CACHE.lock().unwrap();
+    lock.clear();
+}
+
+fn build_transpose_strategy<T: 'static + Copy + Send + Sync>(
+    (rows, cols): (usize, usize),
 ) -> Arc<dyn Permute<T> + 'static> {
     let size = rows * cols;
     let gcd = crate::utils::gcd(rows, cols);
@@ -56,6 +151,55 @@ pub fn transpose<T: Copy + Send + Sync>(matrix: &mut [T], (rows, cols): (usize,
     gw18::transpose(matrix, (rows, cols));
 }
 
+/// Transpose a `rows × cols` matrix in place and return its new shape
+/// `(cols, rows)`.
+///
+/// The result reads as `cols` vectors of length `rows`, as in the six-step
+/// NTT.
+///
+/// # Panics
+///
+/// Panics if `values.len()` does not equal `rows * cols`.
+pub fn reshape_transpose<T: Copy + Send + Sync>(
+    values: &mut [T],
+    (rows, cols): (usize, usize),
+) -> (usize, usize) {
+    assert_eq!(
+        values.len(),
+        rows * cols,
+        "Shape does not match the length."
+    );
+    transpose(values, (rows, cols));
+    (cols, rows)
+}
+
+/// Transpose a `rows × cols` matrix in place and return the column sums of the
+/// original.
+///
+/// The sums are accumulated while copying the matrix to the buffer of
+/// [`copy::transpose`], saving a separate pass.
+///
+/// # Panics
+///
+/// Panics if `values.len()` does not equal `rows * cols`.
+pub fn transpose_with_colsums(values: &mut [Field], (rows, cols): (usize, usize)) -> Vec<Field> {
+    assert_eq!(values.len(), rows * cols);
+    let mut sums = vec![Field::from(0); cols];
+    if values.is_empty() {
+        return sums;
+    }
+    let mut copy = Vec::with_capacity(values.len());
+    for row in values.chunks_exact(cols) {
+        for (sum, &value) in sums.iter_mut().zip(row) {
+            *sum += value;
+        }
+        copy.extend_from_slice(row);
+    }
+    let q = values.len() - 1;
+    permute_wo_oop(&copy, values, 1, |i| if i == q { q } else { i * cols % q });
+    sums
+}
+
 /// Transpose a matrix in place using a buffer.
 pub fn transpose_copy<T: Copy>(matrix: &mut [T], (rows, cols): (usize, usize)) {
     let copy = matrix.to_vec();
@@ -68,14 +212,281 @@ pub fn transpose_copy<T: Copy>(matrix: &mut [T], (rows, cols): (usize, usize)) {
 }
 
 /// Bit-reverse permute an array in place.
+///
+/// Uses a cached index table for small sizes and the blocked
+/// [`bit_reverse::cobra`] from `2^12` on. See also [`bit_reverse_par`].
+///
+/// # Panics
+///
+/// Panics if the length is not a power of two.
 pub fn bit_reverse<T: Copy + Send + Sync>(slice: &mut [T]) {
+    if slice.len() >= bit_reverse::MIN_SIZE {
+        bit_reverse::cobra(slice);
+        return;
+    }
     let copy = slice.to_vec();
-    permute_wo_oop(&copy, slice, 1, permutation::bit_reverse(slice.len()));
+    let table = permutation::bit_reverse_table(slice.len());
+    permute_wo_oop(&copy, slice, 1, |i| table[i]);
+}
+
+/// Bit-reverse permute an array in place by swapping pairs, in parallel.
+///
+/// Bit reversal is an involution, so it is a set of disjoint swaps `i ↔ j`.
+/// Each swap is done by the task that owns the smaller index. This needs no
+/// buffer, but the scattered accesses make it slower than
+/// [`bit_reverse::cobra`] unless there are many cores.
+///
+/// # Panics
+///
+/// Panics if the length is not a power of two.
+pub fn bit_reverse_par<T: Copy + Send + Sync>(values: &mut [T]) {
+    let reverse = permutation::bit_reverse(values.len());
+    let ptr = AtomicPtr::new(values.as_mut_ptr());
+    (0..values.len())
+        .into_par_iter()
+        .with_min_len(1 << 12)
+        .for_each(|i| {
+            let j = reverse(i);
+            if i < j {
+                // Safety: `i` and `j` are in bounds, and since the swaps are
+                // disjoint no other task touches either.
+                unsafe {
+                    let ptr = ptr.load(Ordering::Relaxed);
+                    ptr::swap(ptr.add(i), ptr.add(j));
+                }
+            }
+        });
+}
+
+/// Apply an arbitrary permutation in place, such that afterwards `values[i]`
+/// holds the element previously at `values[perm[i]]`.
+///
+/// Uses cycle-following with a visited bitset. This is slow compared to the
+/// structured permutations, but works for any permutation.
+///
+/// # Panics
+///
+/// Panics if `perm` is not a permutation of `0..values.len()`.
+pub fn apply_permutation<T: Copy>(values: &mut [T], perm: &[usize]) {
+    assert_eq!(
+        values.len(),
+        perm.len(),
+        "Permutation has the wrong length."
+    );
+    let mut visited = vec![0_u64; perm.len().div_ceil(64)];
+    for &i in perm {
+        assert!(i < perm.len(), "Permutation index {i} out of range.");
+        assert!(
+            visited[i / 64] & (1 << (i % 64)) == 0,
+            "Permutation index {i} repeated."
+        );
+        visited[i / 64] |= 1 << (i % 64);
+    }
+
+    // Reuse the bitset, now cleared bits mark visited indices.
+    for start in 0..values.len() {
+        if visited[start / 64] & (1 << (start % 64)) == 0 {
+            continue;
+        }
+        let first = values[start];
+        let mut i = start;
+        loop {
+            visited[i / 64] &= !(1 << (i % 64));
+            let next = perm[i];
+            if next == start {
+                values[i] = first;
+                break;
+            }
+            values[i] = values[next];
+            i = next;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::{
+        proptest,
+        strategy::{Just, Strategy},
+    };
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_bit_reverse() {
+        for size in [1, 2, 16, 1024] {
+            let mut values = (0..size).collect::<Vec<_>>();
+            bit_reverse(&mut values);
+            let bit_reverse = permutation::bit_reverse(size);
+            for (i, &j) in values.iter().enumerate() {
+                assert_eq!(j, bit_reverse(i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_compose() {
+        for (rows, cols) in [(2, 3), (16, 16), (5, 7), (64, 32)] {
+            let compose = Compose::new(
+                transpose_strategy::<usize>((rows, cols)),
+                transpose_strategy((cols, rows)),
+            );
+            assert_eq!(compose.len(), rows * cols);
+            let expected = (0..rows * cols).collect::<Vec<_>>();
+            let mut values = expected.clone();
+            compose.permute(&mut values);
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    fn test_permute_inverse() {
+        fn check(permute: &dyn Permute<usize>) {
+            let expected = (0..permute.len()).collect::<Vec<_>>();
+            let mut values = expected.clone();
+            permute.permute(&mut values);
+            permute.permute_inverse(&mut values);
+            assert_eq!(values, expected);
+            permute.permute_inverse(&mut values);
+            permute.permute(&mut values);
+            assert_eq!(values, expected);
+        }
+        // Square, gcd, cycles and (through `Compose`) the inverse of each.
+        for shape in [(16, 16), (32, 64), (64, 32), (5, 7), (17, 20)] {
+            let strategy = transpose_strategy(shape);
+            check(&strategy);
+            check(&Compose::new(
+                strategy,
+                transpose_strategy((shape.1, shape.0)),
+            ));
+        }
+        check(&gw18::Gw18::new((300, 7)));
+        check(&gw18::Gw18::new((7, 300)));
+        check(&bit_reverse::BitReverse::new(1 << 12));
+    }
+
+    #[test]
+    fn test_transpose_strategy_cached() {
+        for shape in [(3, 5), (16, 16), (300, 7)] {
+            let first = transpose_strategy::<u64>(shape);
+            let second = transpose_strategy::<u64>(shape);
+            assert!(Arc::ptr_eq(&first, &second));
+            let mut a = (0..(shape.0 * shape.1) as u64).collect::<Vec<_>>();
+            let mut b = a.clone();
+            first.permute(&mut a);
+            second.permute(&mut b);
+            assert_eq!(a, b);
+        }
+        let first = transpose_strategy::<u64>((3, 5));
+        clear_transpose_cache();
+        assert!(!Arc::ptr_eq(&first, &transpose_strategy::<u64>((3, 5))));
+
+        // Keyed by element type as well.
+        let strategy = transpose_strategy::<u32>((3, 5));
+        let mut values = (0..15).collect::<Vec<u32>>();
+        let mut expected = values.clone();
+        strategy.permute(&mut values);
+        transpose_copy(&mut expected, (3, 5));
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn test_compose_length() {
+        let _ = Compose::<u64>::new(transpose_strategy((2, 3)), transpose_strategy((2, 2)));
+    }
+
+    #[test]
+    fn test_reshape_transpose() {
+        for shape in [(1, 9), (9, 1), (5, 7), (12, 12), (16, 32)] {
+            let mut values = (0..shape.0 * shape.1).collect::<Vec<_>>();
+            let mut expected = values.clone();
+            transpose_copy(&mut expected, shape);
+            let (rows, cols) = reshape_transpose(&mut values, shape);
+            assert_eq!((rows, cols), (shape.1, shape.0));
+            assert_eq!(values, expected);
+            for (i, row) in values.chunks_exact(cols).enumerate().take(rows) {
+                assert_eq!(row[0], i);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Shape does not match")]
+    fn test_reshape_transpose_length() {
+        let _ = reshape_transpose(&mut [0, 1, 2, 3, 4], (2, 3));
+    }
+
+    #[test]
+    fn test_transpose_with_colsums() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (rows, cols) in [(0, 3), (3, 0), (1, 1), (1, 9), (9, 1), (5, 7), (32, 16)] {
+            let mut values = (0..rows * cols).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let mut expected = values.clone();
+            copy::transpose(&mut expected, (rows, cols));
+            let expected_sums = (0..cols)
+                .map(|j| (0..rows).map(|i| values[i * cols + j]).sum())
+                .collect::<Vec<Field>>();
+            let sums = transpose_with_colsums(&mut values, (rows, cols));
+            assert_eq!(sums, expected_sums);
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    fn test_bit_reverse_par() {
+        for size in [1, 2, 16, 1 << 12, 1 << 13, 1 << 19] {
+            let mut values = (0..size).collect::<Vec<_>>();
+            bit_reverse_par(&mut values);
+            let reverse = permutation::bit_reverse(size);
+            for (i, &j) in values.iter().enumerate() {
+                assert_eq!(j, reverse(i));
+            }
+            bit_reverse_par(&mut values);
+            assert!(values.iter().enumerate().all(|(i, &j)| i == j));
+        }
+    }
+
+    #[test]
+    fn test_apply_permutation() {
+        let perms =
+            (0_usize..1000).prop_flat_map(|n| Just((0..n).collect::<Vec<_>>()).prop_shuffle());
+        proptest!(|(perm in perms)| {
+            let values = (0..perm.len()).map(|i| i * 3 + 1).collect::<Vec<_>>();
+            let expected = perm.iter().map(|&i| values[i]).collect::<Vec<_>>();
+            let mut result = values;
+            apply_permutation(&mut result, &perm);
+            assert_eq!(result, expected);
+        });
+    }
+
+    #[test]
+    fn test_apply_permutation_transpose() {
+        let mut matrix = (0..35).collect::<Vec<_>>();
+        let mut expected = matrix.clone();
+        transpose_copy(&mut expected, (5, 7));
+        let perm = (0..35).map(|i| (i % 5) * 7 + i / 5).collect::<Vec<_>>();
+        apply_permutation(&mut matrix, &perm);
+        assert_eq!(matrix, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "repeated")]
+    fn test_apply_permutation_repeated() {
+        apply_permutation(&mut [0, 1, 2], &[0, 2, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_apply_permutation_out_of_range() {
+        apply_permutation(&mut [0, 1, 2], &[0, 1, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong length")]
+    fn test_apply_permutation_length() {
+        apply_permutation(&mut [0, 1, 2], &[0, 1]);
+    }
 
     #[test]
     #[rustfmt::skip]
@@ -124,3 +535,134 @@ mod tests {
         ]);
     }
 }
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::*;
+    use criterion::{BenchmarkId, Criterion, Throughput};
+
+    pub fn group(criterion: &mut Criterion) {
+        bench_bit_reverse(criterion, 1 << 10);
+        bench_bit_reverse(criterion, 1 << 16);
+        bench_bit_reverse_par(criterion, 1 << 24);
+        sweep(criterion, 1 << 20);
+        square::bench::group(criterion);
+        bit_reverse::bench::group(criterion);
+        bench_transpose_to(criterion, (1 << 9, 1 << 11));
+        bench_transpose_to(criterion, (1000, 1048));
+    }
+
+    /// Out of place versus in place.
+    fn bench_transpose_to(criterion: &mut Criterion, (rows, cols): (usize, usize)) {
+        let size = rows * cols;
+        let src = (0_u64..size as u64).collect::<Vec<_>>();
+        let mut dst = src.clone();
+        let shape = format!("{rows}x{cols}");
+        let mut group = criterion.benchmark_group("permute/transpose_to");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("out_of_place", &shape), |bencher| {
+            bencher.iter(|| transpose_to(&src, &mut dst, (rows, cols)));
+        });
+        group.bench_function(BenchmarkId::new("in_place", &shape), |bencher| {
+            bencher.iter(|| transpose(&mut dst, (rows, cols)));
+        });
+    }
+
+    /// Matrix shapes `(rows, cols)` with `rows * cols == size`, roughly
+    /// logarithmically spaced in aspect ratio.
+    ///
+    /// For each power of two `2^k` in `2..size`, takes the largest divisor of
+    /// `size` not exceeding it.
+    #[must_use]
+    pub fn grid(size: usize) -> Vec<(usize, usize)> {
+        let mut result: Vec<(usize, usize)> = Vec::new();
+        let mut bound = 2;
+        while bound < size {
+            let rows = (2..=bound)
+                .rev()
+                .find(|&d| size.is_multiple_of(d))
+                .unwrap_or(1);
+            if rows > 1 && rows < size && result.last().map(|r| r.0) != Some(rows) {
+                result.push((rows, size / rows));
+            }
+            bound *= 2;
+        }
+        result
+    }
+
+    /// Sweep transpose strategies over the aspect ratios from [`grid`] at a
+    /// fixed total size.
+    ///
+    /// Compares the out-of-place `copy`, in-place `gw18` on all threads and on
+    /// a single thread, and the strategy picked by [`transpose_strategy`].
+    /// Use this to tune the copy and parallel thresholds in `gw18`.
+    pub fn sweep(criterion: &mut Criterion, size: usize) {
+        let serial = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let mut values = (0_u64..size as u64).collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("permute/sweep");
+        group.throughput(Throughput::Elements(size as u64));
+        for (rows, cols) in grid(size) {
+            let shape = format!("{rows}x{cols}");
+            group.bench_function(BenchmarkId::new("copy", &shape), |bencher| {
+                bencher.iter(|| copy::transpose(&mut values, (rows, cols)));
+            });
+            group.bench_function(BenchmarkId::new("gw18", &shape), |bencher| {
+                bencher.iter(|| gw18::transpose(&mut values, (rows, cols)));
+            });
+            group.bench_function(BenchmarkId::new("gw18/serial", &shape), |bencher| {
+                bencher.iter(|| serial.install(|| gw18::transpose(&mut values, (rows, cols))));
+            });
+            let strategy = transpose_strategy((rows, cols));
+            group.bench_function(BenchmarkId::new("strategy", &shape), |bencher| {
+                bencher.iter(|| strategy.permute(&mut values));
+            });
+        }
+    }
+
+    /// The buffered permutation versus parallel swaps.
+    fn bench_bit_reverse_par(criterion: &mut Criterion, size: usize) {
+        let mut values = (0_u64..size as u64).collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("permute/bit_reverse_par");
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::new("copy", size), |bencher| {
+            bencher.iter(|| {
+                let copy = values.clone();
+                permute_wo_oop(&copy, &mut values, 1, permutation::bit_reverse(size));
+            });
+        });
+        group.bench_function(BenchmarkId::new("swap", size), |bencher| {
+            bencher.iter(|| bit_reverse_par(&mut values));
+        });
+    }
+
+    /// Many repeated bit-reversals of the same size, computing the
+    /// permutation each time versus looking it up in the cached table.
+    fn bench_bit_reverse(criterion: &mut Criterion, size: usize) {
+        let mut values = (0_u64..1 << 20).collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("permute/bit_reverse");
+        group.throughput(Throughput::Elements(values.len() as u64));
+        group.bench_function(BenchmarkId::new("computed", size), |bencher| {
+            let mut copy = vec![0; size];
+            bencher.iter(|| {
+                for chunk in values.chunks_exact_mut(size) {
+                    copy.copy_from_slice(chunk);
+                    permute_wo_oop(&copy, chunk, 1, permutation::bit_reverse(size));
+                }
+            });
+        });
+        group.bench_function(BenchmarkId::new("table", size), |bencher| {
+            let mut copy = vec![0; size];
+            bencher.iter(|| {
+                for chunk in values.chunks_exact_mut(size) {
+                    copy.copy_from_slice(chunk);
+                    let table = permutation::bit_reverse_table(size);
+                    permute_wo_oop(&copy, chunk, 1, |i| table[i]);
+                }
+            });
+        });
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::OnceLock;
+
 pub fn transpose(width: usize, height: usize) -> impl Fn(usize) -> usize {
     let q = (width * height).saturating_sub(1);
     move |i| if i == q { q } else { i * width % q }
@@ -11,13 +13,46 @@ pub fn transpose(width: usize, height: usize) -> impl Fn(usize) -> usize {
 pub fn bit_reverse(size: usize) -> impl Fn(usize) -> usize {
     assert!(size.is_power_of_two());
     let shift = usize::BITS - size.trailing_zeros();
-    move |i| i.reverse_bits() >> shift
+    move |i| i.reverse_bits().checked_shr(shift).unwrap_or(0)
+}
+
+/// Largest log size for which [`bit_reverse_table`] caches the table. Beyond
+/// this the table no longer fits in cache and computing indices is as fast.
+pub const MAX_TABLE_LOG_SIZE: u32 = 16;
+
+static BIT_REVERSE_TABLES: [OnceLock<Box<[usize]>>; MAX_TABLE_LOG_SIZE as usize + 1] =
+    [const { OnceLock::new() }; MAX_TABLE_LOG_SIZE as usize + 1];
+
+/// Bit-reversal permutation as a lookup table, computed once per size.
+///
+/// # Panics
+///
+/// Panics if `size` is not a power of two or larger than
+/// `2^MAX_TABLE_LOG_SIZE`.
+pub fn bit_reverse_table(size: usize) -> &'static [usize] {
+    assert!(size.is_power_of_two());
+    let log_size = size.trailing_zeros();
+    assert!(log_size <= MAX_TABLE_LOG_SIZE, "No table for size {size}.");
+    BIT_REVERSE_TABLES[log_size as usize].get_or_init(|| (0..size).map(bit_reverse(size)).collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_bit_reverse_table() {
+        for log_size in 0..=12 {
+            let size = 1 << log_size;
+            let table = bit_reverse_table(size);
+            let bit_reverse = bit_reverse(size);
+            assert_eq!(table.len(), size);
+            for (i, &j) in table.iter().enumerate() {
+                assert_eq!(j, bit_reverse(i));
+            }
+        }
+    }
+
     #[test]
     fn test_transpose() {
         let transpose = transpose(3, 2);
@@ -1,10 +1,14 @@
 //! Transpose square matrices.
 use std::{
+    array,
+    mem::swap,
     ptr::swap_nonoverlapping,
+    slice::{from_raw_parts, from_raw_parts_mut},
     sync::atomic::{AtomicPtr, Ordering},
 };
 
 use super::Permute;
+use rayon::prelude::*;
 
 pub struct SquareTranspose(usize);
 
@@ -23,10 +27,21 @@ impl<T: 'static + Copy + Send + Sync> Permute<T> for SquareTranspose {
         assert_eq!(values.len(), self.0 * self.0);
         transpose(values, self.0);
     }
+
+    fn permute_inverse(&self, values: &mut [T]) {
+        self.permute(values);
+    }
 }
 
-pub fn transpose<T: Copy>(values: &mut [T], size: usize) {
-    // eprintln!("square::transpose({size})");
+/// Transpose a square matrix in place for types that are not `Copy`.
+///
+/// Elements are only moved by swaps, so nothing is duplicated or dropped.
+/// Slower than [`transpose`], which stages tiles in buffers.
+///
+/// # Panics
+///
+/// Panics if `values.len()` does not equal `size * size`.
+pub fn transpose_move<T: Send>(values: &mut [T], size: usize) {
     assert_eq!(values.len(), size * size);
     if size <= 1 {
         return;
@@ -37,7 +52,129 @@ pub fn transpose<T: Copy>(values: &mut [T], size: usize) {
     }
 }
 
-pub(super) unsafe fn transpose_square<T: Copy>(values: *mut T, stride: usize, size: usize) {
+/// Size of the L1 data cache we try to stay within.
+const L1_BYTES: usize = 1 << 15;
+
+/// Matrices with at least this many elements transpose tile rows in parallel.
+const PAR_THRESHOLD: usize = 1 << 16;
+
+pub fn transpose<T: Copy>(values: &mut [T], size: usize) {
+    // eprintln!("square::transpose({size})");
+    assert_eq!(values.len(), size * size);
+    match size {
+        0 | 1 => {}
+        4 => transpose_fixed::<T, 4>(values),
+        8 => transpose_fixed::<T, 8>(values),
+        16 => transpose_fixed::<T, 16>(values),
+        _ => unsafe {
+            transpose_tiled(values.as_mut_ptr(), size, size);
+        },
+    }
+}
+
+/// Transpose an `N × N` matrix through a local copy. With `N` known the loops
+/// fully unroll and the copy can stay in registers, which matters for the
+/// small squares that recursive transposes produce in bulk.
+fn transpose_fixed<T: Copy, const N: usize>(values: &mut [T]) {
+    debug_assert_eq!(values.len(), N * N);
+    let copy: [[T; N]; N] = array::from_fn(|i| array::from_fn(|j| values[i * N + j]));
+    for (i, row) in values.chunks_exact_mut(N).enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = copy[j][i];
+        }
+    }
+}
+
+/// Side length of the largest power-of-two square tile that fits in L1.
+const fn tile_size<T>() -> usize {
+    let mut tile = 1;
+    while (2 * tile) * (2 * tile) * size_of::<T>() <= L1_BYTES {
+        tile *= 2;
+    }
+    tile
+}
+
+/// Transpose by tiles: diagonal tiles in place, off-diagonal tiles swapped
+/// with their mirror image as a pair. One tile of the pair is staged in a
+/// buffer that stays in L1, the other is streamed through by rows.
+unsafe fn transpose_tiled<T: Copy>(values: *mut T, stride: usize, size: usize) {
+    let tile = tile_size::<T>();
+    let tiles = size.div_ceil(tile);
+
+    // Tile row `i` owns the tiles `(i, j)` and `(j, i)` for `j <= i`, so rows
+    // touch disjoint elements.
+    let row = |values: *mut T, i: usize| {
+        let height = tile.min(size - i * tile);
+        let diagonal = values.add(i * tile * stride + i * tile);
+        let mut buffer = Vec::with_capacity(tile * tile);
+        for j in 0..i {
+            transpose_swap_base(
+                values.add(i * tile * stride + j * tile),
+                values.add(j * tile * stride + i * tile),
+                stride,
+                (height, tile),
+                &mut buffer,
+            );
+        }
+        transpose_square_base(diagonal, stride, height);
+    };
+
+    if size * size < PAR_THRESHOLD {
+        for i in 0..tiles {
+            row(values, i);
+        }
+    } else {
+        // Cast to AtomicPtr to allow sharing between threads.
+        let values = AtomicPtr::new(values);
+        (0..tiles)
+            .into_par_iter()
+            .for_each(|i| row(values.load(Ordering::Relaxed), i));
+    }
+}
+
+/// Transpose a square block in place with a triangular loop.
+unsafe fn transpose_square_base<T: Copy>(values: *mut T, stride: usize, size: usize) {
+    for i in 0..size {
+        for j in 0..i {
+            unsafe {
+                // Safety: pointers are non-overlapping and point to valid elements.
+                swap_nonoverlapping(values.add(i * stride + j), values.add(j * stride + i), 1);
+            }
+        }
+    }
+}
+
+/// Swap the `rows × cols` block at `a` with the transpose of the block at `b`.
+///
+/// The block at `b` is staged in `buffer` through contiguous row copies. For
+/// power-of-two strides its rows alias in the cache, so reading it by column
+/// directly would thrash.
+unsafe fn transpose_swap_base<T: Copy>(
+    a: *mut T,
+    b: *mut T,
+    stride: usize,
+    (rows, cols): (usize, usize),
+    buffer: &mut Vec<T>,
+) {
+    buffer.clear();
+    for j in 0..cols {
+        // Safety: the row segment is valid and does not overlap `buffer`.
+        buffer.extend_from_slice(unsafe { from_raw_parts(b.add(j * stride), rows) });
+    }
+    for i in 0..rows {
+        // Safety: the row segment is valid and does not overlap `buffer`.
+        let row = unsafe { from_raw_parts_mut(a.add(i * stride), cols) };
+        for (j, value) in row.iter_mut().enumerate() {
+            swap(value, &mut buffer[j * rows + i]);
+        }
+    }
+    for (j, chunk) in buffer.chunks_exact(rows).enumerate() {
+        // Safety: the row segment is valid and does not overlap `buffer`.
+        unsafe { from_raw_parts_mut(b.add(j * stride), rows) }.copy_from_slice(chunk);
+    }
+}
+
+pub(super) unsafe fn transpose_square<T: Send>(values: *mut T, stride: usize, size: usize) {
     // eprintln!("square::transpose({size})");
     const REC_THRESHOLD: usize = 1 << 4;
     const PAR_THRESHOLD: usize = 1 << 10;
@@ -92,7 +229,7 @@ pub(super) unsafe fn transpose_square<T: Copy>(values: *mut T, stride: usize, si
     }
 }
 
-pub(super) unsafe fn transpose_swap<T: Copy>(
+pub(super) unsafe fn transpose_swap<T: Send>(
     a: *mut T,
     b: *mut T,
     stride: usize,
@@ -171,6 +308,7 @@ pub(super) unsafe fn transpose_swap<T: Copy>(
 #[cfg(test)]
 mod tests {
     use super::{super::copy, *};
+    use crate::test_utils::assert_is_permutation;
 
     #[test]
     fn test_transpose() {
@@ -179,8 +317,73 @@ mod tests {
             let mut matrix = (0_u64..n as u64).collect::<Vec<_>>();
             let mut reference = matrix.clone();
             transpose(&mut matrix, size);
+            assert_is_permutation(&reference, &matrix);
             copy::transpose(&mut reference, (size, size));
             assert_eq!(matrix, reference);
         }
     }
+
+    #[test]
+    fn test_transpose_fixed() {
+        fn test<const N: usize>() {
+            let mut matrix = (0_u64..(N * N) as u64).collect::<Vec<_>>();
+            let mut reference = matrix.clone();
+            transpose_fixed::<_, N>(&mut matrix);
+            copy::transpose(&mut reference, (N, N));
+            assert_eq!(matrix, reference);
+        }
+        test::<4>();
+        test::<8>();
+        test::<16>();
+    }
+
+    #[test]
+    fn test_transpose_large() {
+        let size = 2048;
+        let mut matrix = (0_u64..(size * size) as u64).collect::<Vec<_>>();
+        let mut reference = matrix.clone();
+        transpose(&mut matrix, size);
+        copy::transpose(&mut reference, (size, size));
+        assert_eq!(matrix, reference);
+    }
+}
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench {
+    use super::*;
+    use criterion::{BenchmarkId, Criterion, Throughput};
+
+    pub fn group(criterion: &mut Criterion) {
+        bench_transpose(criterion, 1 << 12);
+        for size in [4, 8, 16] {
+            bench_small(criterion, size);
+        }
+    }
+
+    /// Fixed size kernels versus the generic tiled transpose.
+    fn bench_small(criterion: &mut Criterion, size: usize) {
+        let mut values = (0_u64..(size * size) as u64).collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("permute/square/small");
+        group.throughput(Throughput::Elements((size * size) as u64));
+        group.bench_function(BenchmarkId::new("fixed", size), |bencher| {
+            bencher.iter(|| transpose(&mut values, size));
+        });
+        group.bench_function(BenchmarkId::new("tiled", size), |bencher| {
+            bencher.iter(|| unsafe { transpose_tiled(values.as_mut_ptr(), size, size) });
+        });
+    }
+
+    /// Tiled versus recursive transpose.
+    fn bench_transpose(criterion: &mut Criterion, size: usize) {
+        let mut values = (0_u64..(size * size) as u64).collect::<Vec<_>>();
+        let mut group = criterion.benchmark_group("permute/square");
+        group.throughput(Throughput::Elements((size * size) as u64));
+        group.bench_function(BenchmarkId::new("tiled", size), |bencher| {
+            bencher.iter(|| transpose(&mut values, size));
+        });
+        group.bench_function(BenchmarkId::new("recursive", size), |bencher| {
+            bencher.iter(|| unsafe { transpose_square(values.as_mut_ptr(), size, size) });
+        });
+    }
 }
@@ -0,0 +1,868 @@
+//! Polynomials over [`Field`] as coefficient vectors, from low to high degree.
+
+use crate::{
+    convolve::circular,
+    divisors::{is_divisor, next},
+    field::LazyField,
+    ntt::{intt, negacyclic_mul, ntt},
+    Field,
+};
+use rayon::prelude::*;
+
+/// Tree levels with at least this many coefficients are computed in parallel.
+const PARALLEL_THRESHOLD: usize = 1 << 12;
+
+/// Products where an operand is at most this long use the schoolbook method.
+const NAIVE_THRESHOLD: usize = 32;
+
+/// Divisions where the quotient or divisor is at most this long use the
+/// schoolbook method.
+const FAST_DIV_THRESHOLD: usize = 64;
+
+/// Batch evaluations with at least this many points and coefficients use a
+/// remainder tree instead of Horner's method per point.
+const MULTIPOINT_THRESHOLD: usize = 256;
+
+/// The kind of convolution computed by [`convolve`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConvMode {
+    /// Indices wrap around, `x^n = 1`.
+    Cyclic,
+    /// Plain polynomial multiplication.
+    Linear,
+    /// Indices wrap around with a sign change, `x^n = -1`.
+    Negacyclic,
+}
+
+/// Convolve `a` and `b`, choosing the transform size for `mode`.
+///
+/// Linear convolutions of length `l = a.len() + b.len() - 1` use the smallest
+/// NTT size at least `l`. Cyclic and negacyclic convolutions use an NTT of
+/// the input length if one exists, and otherwise fold a linear convolution.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths in cyclic or negacyclic mode.
+#[must_use]
+pub fn convolve(a: &[Field], b: &[Field], mode: ConvMode) -> Vec<Field> {
+    if mode != ConvMode::Linear {
+        assert_eq!(
+            a.len(),
+            b.len(),
+            "Cyclic and negacyclic convolution need equal lengths."
+        );
+    }
+    let n = a.len();
+    match mode {
+        ConvMode::Linear => mul(a, b),
+        ConvMode::Cyclic if n > NAIVE_THRESHOLD && is_divisor(n) => {
+            let mut result = a.to_vec();
+            let mut b = b.to_vec();
+            ntt(&mut b);
+            circular(&mut result, &b);
+            result
+        }
+        ConvMode::Negacyclic if n > NAIVE_THRESHOLD && is_divisor(2 * n) => negacyclic_mul(a, b),
+        ConvMode::Cyclic | ConvMode::Negacyclic => {
+            let mut result = mul(a, b);
+            let high = result.split_off(n.min(result.len()));
+            result.resize(n, Field::from(0));
+            for (r, h) in result.iter_mut().zip(high) {
+                if mode == ConvMode::Cyclic {
+                    *r += h;
+                } else {
+                    *r -= h;
+                }
+            }
+            result
+        }
+    }
+}
+
+/// Multiply two polynomials, using an NTT for large operands.
+#[must_use]
+pub fn mul(a: &[Field], b: &[Field]) -> Vec<Field> {
+    if a.len().min(b.len()) <= NAIVE_THRESHOLD {
+        return mul_naive(a, b);
+    }
+    let len = a.len() + b.len() - 1;
+    let size = next(len);
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.resize(size, Field::from(0));
+    b.resize(size, Field::from(0));
+    ntt(&mut a);
+    ntt(&mut b);
+    for (a, b) in a.iter_mut().zip(&b) {
+        *a *= *b;
+    }
+    intt(&mut a);
+    a.truncate(len);
+    a
+}
+
+/// Multiply two polynomials using the schoolbook algorithm.
+#[must_use]
+pub fn mul_naive(a: &[Field], b: &[Field]) -> Vec<Field> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut result = vec![Field::from(0); a.len() + b.len() - 1];
+    for (i, &a) in a.iter().enumerate() {
+        for (j, &b) in b.iter().enumerate() {
+            result[i + j] += a * b;
+        }
+    }
+    result
+}
+
+/// Evaluate a polynomial at `x` using Horner's method.
+#[must_use]
+pub fn eval(coefficients: &[Field], x: Field) -> Field {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Field::from(0), |acc, &c| acc * x + c)
+}
+
+/// Evaluate a polynomial at each of `points`.
+///
+/// Few points or short polynomials use [`eval`] per point, in parallel.
+/// Otherwise the polynomial is reduced down the [`subproduct_tree`] of the
+/// points, ending in the constant remainders modulo each `x - x_i`, which
+/// takes `O(n log² n)` instead of `O(n²)`.
+#[must_use]
+pub fn eval_batch(coefficients: &[Field], points: &[Field]) -> Vec<Field> {
+    if points.len().min(coefficients.len()) < MULTIPOINT_THRESHOLD {
+        return points.par_iter().map(|&x| eval(coefficients, x)).collect();
+    }
+    let tree = subproduct_tree(points);
+    let mut result = vec![Field::from(0); points.len()];
+    remainder_tree(
+        coefficients,
+        &tree,
+        0,
+        points.len().next_power_of_two(),
+        &mut result,
+    );
+    result
+}
+
+/// Reduce `poly` modulo `node` of the subproduct `tree` and recurse into its
+/// children, writing the remainders at the leaves to `out`. The node covers
+/// `leaves` leaves, of which the first `out.len()` are points.
+fn remainder_tree(
+    poly: &[Field],
+    tree: &[Vec<Field>],
+    node: usize,
+    leaves: usize,
+    out: &mut [Field],
+) {
+    if out.is_empty() {
+        return;
+    }
+    let (_, rem) = fast_div_rem(poly, &tree[node]);
+    if leaves == 1 {
+        out[0] = rem.first().copied().unwrap_or_default();
+        return;
+    }
+    let half = leaves / 2;
+    let (left, right) = out.split_at_mut(half.min(out.len()));
+    if rem.len() >= PARALLEL_THRESHOLD {
+        rayon::join(
+            || remainder_tree(&rem, tree, 2 * node + 1, half, left),
+            || remainder_tree(&rem, tree, 2 * node + 2, half, right),
+        );
+    } else {
+        remainder_tree(&rem, tree, 2 * node + 1, half, left);
+        remainder_tree(&rem, tree, 2 * node + 2, half, right);
+    }
+}
+
+/// Evaluate each row of a row-major matrix of polynomials at `x`.
+///
+/// `polys` holds consecutive polynomials of `degree_plus_one` coefficients
+/// each. The powers of `x` are computed once and shared by all rows, and rows
+/// are evaluated in parallel. Each row is a [`LazyField::dot`] with the
+/// powers, reduced once instead of per term.
+///
+/// # Panics
+///
+/// Panics if `degree_plus_one` is zero or does not divide `polys.len()`.
+#[must_use]
+pub fn eval_many(polys: &[Field], degree_plus_one: usize, x: Field) -> Vec<Field> {
+    assert!(degree_plus_one > 0);
+    assert_eq!(polys.len() % degree_plus_one, 0);
+    let mut powers = Vec::with_capacity(degree_plus_one);
+    let mut power = Field::from(1);
+    for _ in 0..degree_plus_one {
+        powers.push(power);
+        power *= x;
+    }
+    polys
+        .par_chunks_exact(degree_plus_one)
+        .map(|row| LazyField::dot(row, &powers))
+        .collect()
+}
+
+/// Remove trailing zero coefficients, so the zero polynomial is empty.
+fn trim(mut a: Vec<Field>) -> Vec<Field> {
+    while a.last() == Some(&Field::from(0)) {
+        a.pop();
+    }
+    a
+}
+
+/// Computes `a - b · c`.
+fn sub_mul(a: &[Field], b: &[Field], c: &[Field]) -> Vec<Field> {
+    let mut result = a.to_vec();
+    let product = mul_naive(b, c);
+    if result.len() < product.len() {
+        result.resize(product.len(), Field::from(0));
+    }
+    for (r, p) in result.iter_mut().zip(product) {
+        *r -= p;
+    }
+    trim(result)
+}
+
+/// Scale a polynomial by a constant.
+fn scale(a: &[Field], factor: Field) -> Vec<Field> {
+    a.iter().map(|&a| a * factor).collect()
+}
+
+/// Quotient and remainder of polynomial division using the schoolbook
+/// algorithm. Both results are trimmed of trailing zeros.
+///
+/// # Panics
+///
+/// Panics if `den` is zero.
+#[must_use]
+pub fn div_rem(num: &[Field], den: &[Field]) -> (Vec<Field>, Vec<Field>) {
+    let den = trim(den.to_vec());
+    let Some(&lead) = den.last() else {
+        panic!("Division by the zero polynomial.");
+    };
+    let mut rem = trim(num.to_vec());
+    if rem.len() < den.len() {
+        return (Vec::new(), rem);
+    }
+    let lead_inv = lead.inv();
+    let mut quotient = vec![Field::from(0); rem.len() - den.len() + 1];
+    for i in (0..quotient.len()).rev() {
+        let q = rem[i + den.len() - 1] * lead_inv;
+        quotient[i] = q;
+        for (r, &d) in rem[i..].iter_mut().zip(&den) {
+            *r -= q * d;
+        }
+    }
+    rem.truncate(den.len() - 1);
+    (quotient, trim(rem))
+}
+
+/// Quotient and remainder of polynomial division, the same as [`div_rem`] but
+/// in `O(n log n)` for large inputs.
+///
+/// Reversing the coefficients turns the quotient into the leading terms of a
+/// power series quotient, `rev(q) = rev(num) / rev(den) mod x^k` with `k` the
+/// length of `q`, which is computed with [`inv_mod_xn`].
+///
+/// # Panics
+///
+/// Panics if `den` is zero.
+#[must_use]
+pub fn fast_div_rem(num: &[Field], den: &[Field]) -> (Vec<Field>, Vec<Field>) {
+    let den = trim(den.to_vec());
+    let num = trim(num.to_vec());
+    if den.is_empty() || num.len() < den.len() {
+        return div_rem(&num, &den);
+    }
+    let k = num.len() - den.len() + 1;
+    if k.min(den.len()) <= FAST_DIV_THRESHOLD {
+        return div_rem(&num, &den);
+    }
+
+    let rev_num = num.iter().rev().take(k).copied().collect::<Vec<_>>();
+    let rev_den = den.iter().rev().copied().collect::<Vec<_>>();
+    let mut quotient = mul(&rev_num, &inv_mod_xn(&rev_den, k));
+    quotient.truncate(k);
+    quotient.reverse();
+
+    let product = mul(&quotient, &den);
+    let rem = num
+        .iter()
+        .zip(product)
+        .take(den.len() - 1)
+        .map(|(&n, p)| n - p)
+        .collect();
+    (quotient, trim(rem))
+}
+
+/// Monic greatest common divisor of two polynomials.
+///
+/// The gcd of two zero polynomials is the zero polynomial.
+#[must_use]
+pub fn gcd(a: &[Field], b: &[Field]) -> Vec<Field> {
+    let mut a = trim(a.to_vec());
+    let mut b = trim(b.to_vec());
+    while !b.is_empty() {
+        let (_, rem) = div_rem(&a, &b);
+        a = b;
+        b = rem;
+    }
+    match a.last() {
+        Some(&lead) => scale(&a, lead.inv()),
+        None => a,
+    }
+}
+
+/// Extended Euclidean algorithm. Returns `(g, s, t)` with `g` the monic gcd
+/// of `a` and `b` and `s · a + t · b = g`.
+#[must_use]
+pub fn xgcd(a: &[Field], b: &[Field]) -> (Vec<Field>, Vec<Field>, Vec<Field>) {
+    let (mut r0, mut r1) = (trim(a.to_vec()), trim(b.to_vec()));
+    let (mut s0, mut s1) = (vec![Field::from(1)], Vec::new());
+    let (mut t0, mut t1) = (Vec::new(), vec![Field::from(1)]);
+    while !r1.is_empty() {
+        let (quotient, rem) = div_rem(&r0, &r1);
+        let s2 = sub_mul(&s0, &quotient, &s1);
+        let t2 = sub_mul(&t0, &quotient, &t1);
+        (r0, r1) = (r1, rem);
+        (s0, s1) = (s1, s2);
+        (t0, t1) = (t1, t2);
+    }
+    match r0.last() {
+        Some(&lead) => {
+            let lead_inv = lead.inv();
+            (
+                scale(&r0, lead_inv),
+                scale(&s0, lead_inv),
+                scale(&t0, lead_inv),
+            )
+        }
+        None => (r0, Vec::new(), Vec::new()),
+    }
+}
+
+/// Inverse of the power series `f` modulo `x^n`.
+///
+/// Uses Newton iteration `g ← g · (2 - f · g)`, which doubles the number of
+/// correct coefficients each step, with NTT based products.
+///
+/// # Panics
+///
+/// Panics if the constant coefficient `f[0]` is zero or missing.
+#[must_use]
+pub fn inv_mod_xn(f: &[Field], n: usize) -> Vec<Field> {
+    let constant = f.first().copied().unwrap_or_default();
+    assert!(
+        constant != Field::from(0),
+        "Power series with zero constant term has no inverse."
+    );
+    let mut g = vec![constant.inv()];
+    let mut precision = 1;
+    while precision < n {
+        precision = (2 * precision).min(n);
+        let mut error = mul(&f[..f.len().min(precision)], &g);
+        error.resize(precision, Field::from(0));
+        for e in &mut error {
+            *e = -*e;
+        }
+        error[0] += Field::from(2);
+        g = mul(&g, &error);
+        g.resize(precision, Field::from(0));
+    }
+    g.truncate(n);
+    g
+}
+
+/// Subproduct tree of `∏ (x - x_i)` over the given points.
+///
+/// The tree is complete binary and returned in heap order: the root is at
+/// index `0` and the children of node `i` are at `2i + 1` and `2i + 2`. The
+/// leaves are the linear factors `x - x_i`, padded with the constant `1` up
+/// to a power of two. Each node is the product of its children, so the root
+/// is the vanishing polynomial of `points`.
+///
+/// Siblings are multiplied in parallel once a level is large enough.
+#[must_use]
+pub fn subproduct_tree(points: &[Field]) -> Vec<Vec<Field>> {
+    let leaves = points.len().next_power_of_two();
+    let mut level = points
+        .iter()
+        .map(|&x| vec![-x, Field::from(1)])
+        .chain(std::iter::repeat_with(|| vec![Field::from(1)]))
+        .take(leaves)
+        .collect::<Vec<_>>();
+
+    let mut levels = Vec::new();
+    while level.len() > 1 {
+        let size = level.iter().map(Vec::len).sum::<usize>();
+        let next = if size >= PARALLEL_THRESHOLD {
+            level
+                .par_chunks_exact(2)
+                .map(|pair| mul(&pair[0], &pair[1]))
+                .collect::<Vec<_>>()
+        } else {
+            level
+                .chunks_exact(2)
+                .map(|pair| mul(&pair[0], &pair[1]))
+                .collect::<Vec<_>>()
+        };
+        levels.push(level);
+        level = next;
+    }
+    levels.push(level);
+    levels.into_iter().rev().flatten().collect()
+}
+
+/// The polynomial of degree less than `n` through the `n` given `(x, y)`
+/// points, returned as `n` coefficients.
+///
+/// Few points use Lagrange's formula directly in `O(n²)`. Otherwise the
+/// weights `y_i / M'(x_i)`, with `M` the vanishing polynomial of the `x_i`,
+/// are combined up the [`subproduct_tree`], where each node is
+/// `left · M_right + right · M_left`, in `O(n log² n)`.
+///
+/// # Panics
+///
+/// Panics if two points have the same `x`.
+#[must_use]
+pub fn interpolate(points: &[(Field, Field)]) -> Vec<Field> {
+    let n = points.len();
+    if n < MULTIPOINT_THRESHOLD {
+        return interpolate_naive(points);
+    }
+    let xs = points.iter().map(|&(x, _)| x).collect::<Vec<_>>();
+    let tree = subproduct_tree(&xs);
+    let derivative = tree[0]
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| c * Field::from(i as u64))
+        .collect::<Vec<_>>();
+    let weights = eval_batch(&derivative, &xs)
+        .into_iter()
+        .zip(points)
+        .map(|(d, &(_, y))| {
+            assert!(d != Field::from(0), "Duplicate x-coordinate.");
+            y / d
+        })
+        .collect::<Vec<_>>();
+    let mut result = lagrange_tree(&tree, 0, n.next_power_of_two(), &weights);
+    result.resize(n, Field::from(0));
+    result
+}
+
+/// Lagrange's formula `Σ y_i ∏_(j ≠ i) (x - x_j) / (x_i - x_j)`.
+fn interpolate_naive(points: &[(Field, Field)]) -> Vec<Field> {
+    let n = points.len();
+    let mut vanishing = vec![Field::from(1)];
+    for &(x, _) in points {
+        vanishing = mul_naive(&vanishing, &[-x, Field::from(1)]);
+    }
+    let mut result = vec![Field::from(0); n];
+    for &(x, y) in points {
+        // Synthetic division of the vanishing polynomial by `x - x_i`.
+        let mut basis = vec![Field::from(0); n];
+        let mut carry = Field::from(0);
+        for k in (0..n).rev() {
+            carry = vanishing[k + 1] + carry * x;
+            basis[k] = carry;
+        }
+        let denominator = eval(&basis, x);
+        assert!(denominator != Field::from(0), "Duplicate x-coordinate.");
+        let factor = y / denominator;
+        for (r, &b) in result.iter_mut().zip(&basis) {
+            *r += b * factor;
+        }
+    }
+    result
+}
+
+/// Combine the `weights` up from `node` of the subproduct `tree`, which
+/// covers `leaves` leaves of which the first `weights.len()` are points.
+fn lagrange_tree(tree: &[Vec<Field>], node: usize, leaves: usize, weights: &[Field]) -> Vec<Field> {
+    if weights.is_empty() {
+        return Vec::new();
+    }
+    if leaves == 1 {
+        return vec![weights[0]];
+    }
+    let half = leaves / 2;
+    let (left, right) = weights.split_at(half.min(weights.len()));
+    let combine = |weights, child, sibling: usize| {
+        mul(&lagrange_tree(tree, child, half, weights), &tree[sibling])
+    };
+    let (mut left, right) = if tree[node].len() >= PARALLEL_THRESHOLD {
+        rayon::join(
+            || combine(left, 2 * node + 1, 2 * node + 2),
+            || combine(right, 2 * node + 2, 2 * node + 1),
+        )
+    } else {
+        (
+            combine(left, 2 * node + 1, 2 * node + 2),
+            combine(right, 2 * node + 2, 2 * node + 1),
+        )
+    };
+    if left.len() < right.len() {
+        left.resize(right.len(), Field::from(0));
+    }
+    for (l, r) in left.iter_mut().zip(right) {
+        *l += r;
+    }
+    left
+}
+
+/// Solve `C x = rhs` for the circulant matrix `C` with first row
+/// `first_row`, i.e. `C_ij = first_row[(j - i) mod n]`.
+///
+/// The product `C x` is the cyclic convolution of `x` with the first column,
+/// so the solution divides the spectra pointwise. Returns `None` if a
+/// spectral value of the first column is zero, which is when `C` is singular.
+///
+/// # Panics
+///
+/// Panics if the lengths differ or do not divide the multiplicative group
+/// order.
+#[must_use]
+pub fn circulant_solve(first_row: &[Field], rhs: &[Field]) -> Option<Vec<Field>> {
+    assert_eq!(first_row.len(), rhs.len());
+    let n = first_row.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+    let mut column = first_row.to_vec();
+    column[1..].reverse();
+    ntt(&mut column);
+    if column.contains(&Field::from(0)) {
+        return None;
+    }
+    let mut result = rhs.to_vec();
+    ntt(&mut result);
+    for (x, &c) in result.iter_mut().zip(&column) {
+        *x /= c;
+    }
+    intt(&mut result);
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_mul_naive() {
+        let a = [Field::from(1), Field::from(2)];
+        let b = [Field::from(3), Field::from(4), Field::from(5)];
+        let expected = [3, 10, 13, 10].map(Field::from);
+        assert_eq!(mul_naive(&a, &b), expected);
+        assert_eq!(mul_naive(&a, &[]), []);
+    }
+
+    #[test]
+    fn test_mul() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (a_len, b_len) in [
+            (0, 0),
+            (0, 100),
+            (1, 1),
+            (1, 200),
+            (33, 33),
+            (64, 100),
+            (257, 500),
+        ] {
+            let a = rand_poly(&mut rng, a_len);
+            let b = rand_poly(&mut rng, b_len);
+            assert_eq!(mul(&a, &b), mul_naive(&a, &b));
+            assert_eq!(mul(&b, &a), mul_naive(&a, &b));
+        }
+    }
+
+    fn field_vec(coefficients: &[i32]) -> Vec<Field> {
+        coefficients.iter().map(|&c| Field::from(c)).collect()
+    }
+
+    fn rand_poly(rng: &mut StdRng, len: usize) -> Vec<Field> {
+        (0..len).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_convolve() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (a_len, b_len) in [(0, 0), (1, 5), (7, 3), (40, 50), (100, 257), (500, 500)] {
+            let a = rand_poly(&mut rng, a_len);
+            let b = rand_poly(&mut rng, b_len);
+            assert_eq!(convolve(&a, &b, ConvMode::Linear), mul_naive(&a, &b));
+        }
+        for n in [1, 3, 8, 33, 64, 100, 240, 257] {
+            let a = rand_poly(&mut rng, n);
+            let b = rand_poly(&mut rng, n);
+            let mut cyclic = vec![Field::from(0); n];
+            let mut negacyclic = vec![Field::from(0); n];
+            for (k, &c) in mul_naive(&a, &b).iter().enumerate() {
+                cyclic[k % n] += c;
+                if k < n {
+                    negacyclic[k] += c;
+                } else {
+                    negacyclic[k - n] -= c;
+                }
+            }
+            assert_eq!(convolve(&a, &b, ConvMode::Cyclic), cyclic);
+            assert_eq!(convolve(&a, &b, ConvMode::Negacyclic), negacyclic);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "equal lengths")]
+    fn test_convolve_cyclic_mismatched() {
+        let _ = convolve(
+            &field_vec(&[1, 2, 3]),
+            &field_vec(&[1, 2]),
+            ConvMode::Cyclic,
+        );
+    }
+
+    #[test]
+    fn test_eval() {
+        let poly = field_vec(&[1, 2, 3]);
+        assert_eq!(eval(&poly, Field::from(2)), Field::from(17));
+        assert_eq!(eval(&[], Field::from(2)), Field::from(0));
+    }
+
+    #[test]
+    fn test_eval_batch() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (len, count) in [
+            (0, 0),
+            (0, 300),
+            (5, 1000),
+            (1000, 3),
+            (300, 300),
+            (1000, 777),
+        ] {
+            let poly = rand_poly(&mut rng, len);
+            let points = rand_poly(&mut rng, count);
+            let expected = points
+                .iter()
+                .map(|&x| {
+                    let mut power = Field::from(1);
+                    let mut sum = Field::from(0);
+                    for &c in &poly {
+                        sum += c * power;
+                        power *= x;
+                    }
+                    sum
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(eval_batch(&poly, &points), expected, "{len} at {count}");
+        }
+    }
+
+    #[test]
+    fn test_eval_many() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (rows, degree_plus_one) in [(0, 3), (1, 1), (5, 8), (100, 17)] {
+            let polys = rand_poly(&mut rng, rows * degree_plus_one);
+            let x = rng.gen();
+            let expected = polys
+                .chunks_exact(degree_plus_one)
+                .map(|row| eval(row, x))
+                .collect::<Vec<_>>();
+            assert_eq!(eval_many(&polys, degree_plus_one, x), expected);
+        }
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (n, d) in [(0, 1), (3, 5), (5, 5), (10, 3), (20, 1)] {
+            let num = rand_poly(&mut rng, n);
+            let den = rand_poly(&mut rng, d);
+            let (quotient, rem) = div_rem(&num, &den);
+            assert!(rem.len() < den.len());
+            let mut expected = mul_naive(&quotient, &den);
+            expected.resize(expected.len().max(rem.len()), Field::from(0));
+            for (e, &r) in expected.iter_mut().zip(&rem) {
+                *e += r;
+            }
+            assert_eq!(trim(expected), trim(num));
+        }
+    }
+
+    #[test]
+    fn test_fast_div_rem() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let t = FAST_DIV_THRESHOLD;
+        for (n, d) in [
+            (0, 1),
+            (10, 3),
+            (2 * t, t),
+            (2 * t, t + 1),
+            (2 * t + 1, t + 1),
+            (2 * t + 2, t + 1),
+            (1000, 1),
+            (1000, 300),
+            (1000, 999),
+            (3000, 1000),
+        ] {
+            let num = rand_poly(&mut rng, n);
+            let den = rand_poly(&mut rng, d);
+            assert_eq!(fast_div_rem(&num, &den), div_rem(&num, &den));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "zero polynomial")]
+    fn test_fast_div_rem_zero() {
+        let _ = fast_div_rem(&field_vec(&[1, 2]), &field_vec(&[0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "zero polynomial")]
+    fn test_div_rem_zero() {
+        let _ = div_rem(&field_vec(&[1, 2]), &field_vec(&[0]));
+    }
+
+    #[test]
+    fn test_gcd() {
+        let a = field_vec(&[-1, 0, 1]);
+        let b = field_vec(&[-3, 3]);
+        assert_eq!(gcd(&a, &b), field_vec(&[-1, 1]));
+        assert_eq!(gcd(&b, &a), field_vec(&[-1, 1]));
+        assert_eq!(gcd(&a, &[]), a);
+        assert_eq!(gcd(&[], &b), field_vec(&[-1, 1]));
+        assert_eq!(gcd(&[], &[]), []);
+        assert_eq!(
+            gcd(&field_vec(&[1, 1]), &field_vec(&[2, 1])),
+            field_vec(&[1])
+        );
+    }
+
+    #[test]
+    fn test_xgcd() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let common = rand_poly(&mut rng, 4);
+        for (a_len, b_len) in [(0, 0), (0, 3), (3, 0), (5, 7), (8, 2), (10, 10)] {
+            let a = mul_naive(&rand_poly(&mut rng, a_len), &common);
+            let b = mul_naive(&rand_poly(&mut rng, b_len), &common);
+            let (g, cofactor_a, cofactor_b) = xgcd(&a, &b);
+            assert_eq!(g, gcd(&a, &b));
+            let mut bezout = mul_naive(&cofactor_a, &a);
+            let product_b = mul_naive(&cofactor_b, &b);
+            bezout.resize(bezout.len().max(product_b.len()), Field::from(0));
+            for (x, y) in bezout.iter_mut().zip(product_b) {
+                *x += y;
+            }
+            assert_eq!(trim(bezout), g);
+        }
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for n in [0, 1, 2, 10, 255, 256, 300, 1000] {
+            let poly = rand_poly(&mut rng, n);
+            let points = (0..n)
+                .map(|_| {
+                    let x = rng.gen();
+                    (x, eval(&poly, x))
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(interpolate(&points), poly, "{n} points");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate x-coordinate")]
+    fn test_interpolate_duplicate() {
+        let _ = interpolate(&[
+            (Field::from(1), Field::from(2)),
+            (Field::from(1), Field::from(3)),
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate x-coordinate")]
+    fn test_interpolate_duplicate_large() {
+        let mut points = (0..MULTIPOINT_THRESHOLD)
+            .map(|i| (Field::from(i as u64), Field::from(0)))
+            .collect::<Vec<_>>();
+        points[7].0 = Field::from(3);
+        let _ = interpolate(&points);
+    }
+
+    #[test]
+    fn test_circulant_solve() {
+        // [2 1 0; 0 2 1; 1 0 2] · [1 2 3] = [4 7 7]
+        let solution = circulant_solve(&field_vec(&[2, 1, 0]), &field_vec(&[4, 7, 7]));
+        assert_eq!(solution, Some(field_vec(&[1, 2, 3])));
+
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        let n = 240;
+        let row = rand_poly(&mut rng, n);
+        let rhs = rand_poly(&mut rng, n);
+        let x = circulant_solve(&row, &rhs).unwrap();
+        for i in 0..n {
+            let value = (0..n).map(|j| row[(j + n - i) % n] * x[j]).sum::<Field>();
+            assert_eq!(value, rhs[i]);
+        }
+    }
+
+    #[test]
+    fn test_circulant_solve_singular() {
+        // Rows sum to zero, so the all-ones vector is in the kernel.
+        let row = field_vec(&[1, -1, 0, 0]);
+        assert_eq!(circulant_solve(&row, &field_vec(&[1, 2, 3, 4])), None);
+        assert_eq!(circulant_solve(&[Field::from(0)], &[Field::from(1)]), None);
+    }
+
+    #[test]
+    fn test_inv_mod_xn() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for (len, n) in [(1, 0), (1, 1), (1, 7), (3, 10), (40, 33), (100, 1000)] {
+            let f = rand_poly(&mut rng, len);
+            let g = inv_mod_xn(&f, n);
+            assert_eq!(g.len(), n);
+            let mut product = mul(&f, &g);
+            product.resize(n, Field::from(0));
+            let mut expected = vec![Field::from(0); n];
+            if n > 0 {
+                expected[0] = Field::from(1);
+            }
+            assert_eq!(product, expected);
+        }
+        // 1 / (1 - x) = 1 + x + x^2 + ...
+        assert_eq!(
+            inv_mod_xn(&field_vec(&[1, -1]), 4),
+            field_vec(&[1, 1, 1, 1])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "zero constant term")]
+    fn test_inv_mod_xn_zero() {
+        let _ = inv_mod_xn(&field_vec(&[0, 1]), 4);
+    }
+
+    #[test]
+    fn test_subproduct_tree() {
+        let mut rng = StdRng::seed_from_u64(Field::MODULUS);
+        for size in [0_usize, 1, 2, 3, 5, 8, 100, 1000] {
+            let points = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+            let tree = subproduct_tree(&points);
+            assert_eq!(tree.len(), 2 * size.next_power_of_two() - 1);
+
+            let mut expected = vec![Field::from(1)];
+            for &x in &points {
+                expected = mul_naive(&expected, &[-x, Field::from(1)]);
+            }
+            assert_eq!(tree[0], expected);
+
+            for (i, node) in tree.iter().enumerate().take(tree.len() / 2) {
+                assert_eq!(*node, mul_naive(&tree[2 * i + 1], &tree[2 * i + 2]));
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Helpers for testing code that uses this crate. Requires the `test-utils`
+//! feature.
+
+use crate::{
+    ntt::{intt, ntt},
+    Field,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fmt::Debug;
+
+/// Assert that [`ntt`] followed by [`intt`] is the identity on random data of
+/// the given size.
+///
+/// The data is seeded by `size`, so failures are reproducible.
+///
+/// # Panics
+///
+/// Panics if the roundtrip fails or `size` is not a supported NTT size.
+#[track_caller]
+pub fn assert_ntt_roundtrip(size: usize) {
+    let mut rng = StdRng::seed_from_u64(size as u64);
+    let expected = (0..size).map(|_| rng.gen()).collect::<Vec<Field>>();
+    let mut values = expected.clone();
+    ntt(&mut values);
+    intt(&mut values);
+    assert_eq!(values, expected, "NTT roundtrip failed for size {size}");
+}
+
+/// Assert that the NTT roundtrips on random data for each of the given sizes.
+///
+/// ```
+/// goldilocks_ntt::assert_ntt_roundtrip!(16, 240);
+/// ```
+#[macro_export]
+macro_rules! assert_ntt_roundtrip {
+    ($($size:expr),+ $(,)?) => {
+        $( $crate::test_utils::assert_ntt_roundtrip($size); )+
+    };
+}
+
+/// Assert that `after` is a rearrangement of `before`, i.e. that no element
+/// was lost or duplicated.
+///
+/// This is weaker than comparing to a reference result, but catches a
+/// distinct class of bugs and needs no reference.
+///
+/// # Panics
+///
+/// Panics if the lengths differ or the multisets of elements differ.
+#[track_caller]
+pub fn assert_is_permutation<T: Clone + Ord + Debug>(before: &[T], after: &[T]) {
+    assert_eq!(before.len(), after.len(), "Lengths differ.");
+    let mut before = before.to_vec();
+    let mut after = after.to_vec();
+    before.sort_unstable();
+    after.sort_unstable();
+    if let Some(i) = (0..before.len()).find(|&i| before[i] != after[i]) {
+        panic!(
+            "Not a permutation: sorted elements differ at {i}, {:?} versus {:?}.",
+            before[i], after[i]
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assert_ntt_roundtrip() {
+        assert_ntt_roundtrip(1);
+        crate::assert_ntt_roundtrip!(2, 3, 64, 257, 1 << 12);
+    }
+
+    #[test]
+    fn test_assert_is_permutation() {
+        assert_is_permutation(&[1, 2, 2, 3], &[2, 3, 1, 2]);
+        assert_is_permutation::<u8>(&[], &[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a permutation")]
+    fn test_assert_is_permutation_duplicate() {
+        assert_is_permutation(&[1, 2, 3], &[1, 2, 2]);
+    }
+}
@@ -0,0 +1,189 @@
+//! Fiat-Shamir transcripts using KangarooTwelve. Requires the `hash` feature.
+
+use crate::Field;
+use rust_crypto_k12::{
+    digest::{ExtendableOutput, Update, XofReader},
+    KangarooTwelve,
+};
+
+const CUSTOMIZATION: &[u8] = b"goldilocks-ntt/transcript";
+const HASH_TO_FIELD_CUSTOMIZATION: &[u8] = b"goldilocks-ntt/hash-to-field";
+
+const TAG_LABEL: u8 = 0;
+const TAG_BYTES: u8 = 1;
+const TAG_FIELD: u8 = 2;
+const TAG_SQUEEZE: u8 = 3;
+
+/// A transcript that absorbs messages and squeezes deterministic challenges.
+///
+/// Messages are framed with a tag and their length, so different sequences of
+/// absorbs always produce different challenges. Each squeeze hashes the
+/// transcript so far and replaces it by a 32 byte digest.
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    buffer: Vec<u8>,
+}
+
+impl Transcript {
+    /// Start a new transcript separated by the domain `label`.
+    #[must_use]
+    pub fn new(label: &[u8]) -> Self {
+        let mut result = Self { buffer: Vec::new() };
+        result.append(TAG_LABEL, label.len(), label);
+        result
+    }
+
+    /// Absorb a byte message.
+    pub fn absorb_bytes(&mut self, message: &[u8]) {
+        self.append(TAG_BYTES, message.len(), message);
+    }
+
+    /// Absorb field elements, using their canonical values.
+    pub fn absorb(&mut self, values: &[Field]) {
+        self.append(TAG_FIELD, values.len(), &[]);
+        for value in values {
+            self.buffer
+                .extend_from_slice(&u64::from(value).to_le_bytes());
+        }
+    }
+
+    /// Squeeze a challenge.
+    ///
+    /// The challenge is 128 bits of hash output reduced modulo `p`, which has
+    /// statistical distance less than `2^-64` from uniform.
+    pub fn squeeze(&mut self) -> Field {
+        let mut hasher = KangarooTwelve::new_with_customization(CUSTOMIZATION);
+        hasher.update(&self.buffer);
+        hasher.update(&[TAG_SQUEEZE]);
+        let mut output = [0; 48];
+        hasher.finalize_xof().read(&mut output);
+
+        let (state, challenge) = output.split_at(32);
+        self.buffer.clear();
+        self.buffer.extend_from_slice(state);
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(challenge);
+        Field::from(u128::from_le_bytes(bytes))
+    }
+
+    /// Squeeze `count` challenges.
+    pub fn squeeze_many(&mut self, count: usize) -> Vec<Field> {
+        (0..count).map(|_| self.squeeze()).collect()
+    }
+
+    fn append(&mut self, tag: u8, length: usize, message: &[u8]) {
+        self.buffer.push(tag);
+        self.buffer
+            .extend_from_slice(&(length as u64).to_le_bytes());
+        self.buffer.extend_from_slice(message);
+    }
+}
+
+/// Hash `bytes` to a field element, the first of [`hash_to_field_many`].
+#[must_use]
+pub fn hash_to_field(bytes: &[u8]) -> Field {
+    let mut result = [Field::from(0)];
+    hash_to_field_many(bytes, &mut result);
+    result[0]
+}
+
+/// Hash `bytes` to field elements filling `output`.
+///
+/// Each element is 128 bits of KangarooTwelve output reduced modulo `p`. As
+/// `2^128 mod p < 2^64`, the statistical distance from uniform is less than
+/// `2^64 / 2^128 = 2^-64` per element.
+pub fn hash_to_field_many(bytes: &[u8], output: &mut [Field]) {
+    let mut hasher = KangarooTwelve::new_with_customization(HASH_TO_FIELD_CUSTOMIZATION);
+    hasher.update(bytes);
+    // The reader only supports a single read.
+    let mut bytes = vec![0; 16 * output.len()];
+    hasher.finalize_xof().read(&mut bytes);
+    for (value, wide) in output.iter_mut().zip(bytes.chunks_exact(16)) {
+        let mut array = [0; 16];
+        array.copy_from_slice(wide);
+        *value = Field::from(u128::from_le_bytes(array));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Transcript {
+        let mut transcript = Transcript::new(b"test");
+        transcript.absorb(&[Field::from(1), Field::from(2), Field::from(3)]);
+        transcript.absorb_bytes(b"message");
+        transcript
+    }
+
+    #[test]
+    fn test_pinned() {
+        let mut transcript = example();
+        assert_eq!(
+            transcript.squeeze(),
+            Field::from(883_968_033_444_298_718_u64)
+        );
+        assert_eq!(
+            transcript.squeeze(),
+            Field::from(16_009_444_164_304_420_384_u64)
+        );
+    }
+
+    #[test]
+    fn test_deterministic() {
+        assert_eq!(example().squeeze_many(4), example().squeeze_many(4));
+        let challenges = example().squeeze_many(4);
+        for i in 0..4 {
+            for j in 0..i {
+                assert_ne!(challenges[i], challenges[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        assert_eq!(
+            hash_to_field(b""),
+            Field::from(6_594_816_990_606_619_070_u64)
+        );
+        assert_eq!(hash_to_field(b"message"), hash_to_field(b"message"));
+        assert_ne!(hash_to_field(b"message"), hash_to_field(b"massage"));
+
+        let mut many = [Field::from(0); 4];
+        hash_to_field_many(b"message", &mut many);
+        assert_eq!(many[0], hash_to_field(b"message"));
+        for i in 0..4 {
+            for j in 0..i {
+                assert_ne!(many[i], many[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_distinct() {
+        let expected = example().squeeze();
+
+        let mut transcript = Transcript::new(b"other");
+        transcript.absorb(&[Field::from(1), Field::from(2), Field::from(3)]);
+        transcript.absorb_bytes(b"message");
+        assert_ne!(transcript.squeeze(), expected);
+
+        let mut transcript = Transcript::new(b"test");
+        transcript.absorb(&[Field::from(1), Field::from(2), Field::from(4)]);
+        transcript.absorb_bytes(b"message");
+        assert_ne!(transcript.squeeze(), expected);
+
+        // Moving data between messages changes the framing.
+        let mut transcript = Transcript::new(b"test");
+        transcript.absorb(&[Field::from(1), Field::from(2)]);
+        transcript.absorb(&[Field::from(3)]);
+        transcript.absorb_bytes(b"message");
+        assert_ne!(transcript.squeeze(), expected);
+
+        let mut transcript = Transcript::new(b"test");
+        transcript.absorb(&[Field::from(1), Field::from(2), Field::from(3)]);
+        transcript.absorb_bytes(b"mess");
+        transcript.absorb_bytes(b"age");
+        assert_ne!(transcript.squeeze(), expected);
+    }
+}
@@ -1,5 +1,5 @@
 use core::mem::swap;
-use std::ops::Range;
+use core::ops::Range;
 
 /// Force the compiler to generate a branch instruction instead of conditionals.
 /// Trick from [plonky2].